@@ -0,0 +1,150 @@
+use crate::prelude::*;
+
+// Frame sequences: a camera moving along keyframes and the timing of each frame. Scene time is in
+// seconds, so animated transforms and deforming meshes keyed in seconds play back at the frame rate.
+
+// ----- Camera Path -----
+
+/// Where the camera is at a point in time. Without a focus distance, the camera focuses on its
+/// look-at point.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraKeyframe {
+    time: f64,
+    look_from: Point3,
+    look_at: Point3,
+    v_fov: f64,
+    focus_dist: Option<f64>,
+}
+
+/// The camera settings at a point in time, interpolated from a path.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraPose {
+    pub look_from: Point3,
+    pub look_at: Point3,
+    pub v_fov: f64,
+    pub focus_dist: f64,
+}
+
+impl CameraKeyframe {
+    /// Constructor from the camera position, the point it looks at and its vertical view angle in degrees.
+    pub fn new(time: f64, look_from: Point3, look_at: Point3, v_fov: f64) -> Self {
+        Self { time, look_from, look_at, v_fov, focus_dist: None }
+    }
+
+    /// Set the distance to the plane of perfect focus.
+    pub fn with_focus_dist(mut self, focus_dist: f64) -> Self {
+        self.focus_dist = Some(focus_dist);
+        self
+    }
+
+    /// The time of the keyframe.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Focus distance, defaulting to the look-at point.
+    fn focus_dist(&self) -> f64 {
+        self.focus_dist.unwrap_or_else(|| (self.look_at - self.look_from).length())
+    }
+}
+
+/// A camera moving through keyframes, interpolated linearly and held before the first and after
+/// the last. Focus is interpolated too, so a rack focus is two keyframes with different distances.
+#[derive(Debug, Clone)]
+pub struct CameraPath {
+    keys: Vec<CameraKeyframe>, // Sorted by time
+}
+
+impl CameraPath {
+    /// Constructor from keyframes in any order. Panics if there are none.
+    pub fn new(mut keys: Vec<CameraKeyframe>) -> Self {
+        assert!(!keys.is_empty(), "a camera path needs at least one keyframe");
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self { keys }
+    }
+
+    /// The camera settings at the given time.
+    pub fn at(&self, time: f64) -> CameraPose {
+        let next = self.keys.partition_point(|key| key.time <= time);
+        let (a, b) = if next == 0 {
+            (&self.keys[0], &self.keys[0])
+        } else if next == self.keys.len() {
+            (&self.keys[next - 1], &self.keys[next - 1])
+        } else {
+            (&self.keys[next - 1], &self.keys[next])
+        };
+
+        let span = b.time - a.time;
+        let t = if span > 0.0 { (time - a.time) / span } else { 0.0 };
+        CameraPose {
+            look_from: a.look_from + t * (b.look_from - a.look_from),
+            look_at: a.look_at + t * (b.look_at - a.look_at),
+            v_fov: a.v_fov + t * (b.v_fov - a.v_fov),
+            focus_dist: a.focus_dist() + t * (b.focus_dist() - a.focus_dist()),
+        }
+    }
+}
+
+// ----- Sequence -----
+
+/// Settings for rendering numbered frames of an animation into a directory.
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    pub frames: u32,            // Number of frames to render
+    pub frame_rate: f64,        // Frames per second of scene time
+    pub start_time: f64,        // Scene time of the first frame
+    pub shutter_angle: f64,     // Fraction of each frame the shutter is open, in degrees (360 = whole frame, 0 = no blur)
+    pub output_dir: String,     // Directory the frames are written to, created if missing
+    pub camera_path: Option<CameraPath>, // Camera motion, or None to keep the camera where it is
+}
+
+impl Sequence {
+    /// Constructor for a number of frames at a frame rate, with a 180 degree shutter, written to
+    /// the "frames" directory.
+    pub fn new(frames: u32, frame_rate: f64) -> Self {
+        assert!(frame_rate > 0.0, "frame rate must be positive");
+        Self {
+            frames,
+            frame_rate,
+            start_time: 0.0,
+            shutter_angle: 180.0,
+            output_dir: "frames".to_string(),
+            camera_path: None,
+        }
+    }
+
+    /// Start the sequence at a later scene time.
+    pub fn with_start_time(mut self, start_time: f64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Set the shutter angle in degrees.
+    pub fn with_shutter_angle(mut self, shutter_angle: f64) -> Self {
+        self.shutter_angle = shutter_angle.clamp(0.0, 360.0);
+        self
+    }
+
+    /// Set the directory the frames are written to.
+    pub fn with_output_dir(mut self, output_dir: &str) -> Self {
+        self.output_dir = output_dir.to_string();
+        self
+    }
+
+    /// Move the camera along a path.
+    pub fn with_camera_path(mut self, camera_path: CameraPath) -> Self {
+        self.camera_path = Some(camera_path);
+        self
+    }
+
+    /// Scene time at which a frame's shutter opens.
+    pub fn frame_time(&self, frame: u32) -> f64 {
+        self.start_time + frame as f64 / self.frame_rate
+    }
+
+    /// Scene times the shutter opens and closes for a frame.
+    pub fn shutter(&self, frame: u32) -> (f64, f64) {
+        let open = self.frame_time(frame);
+        (open, open + self.shutter_angle / 360.0 / self.frame_rate)
+    }
+}
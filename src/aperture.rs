@@ -0,0 +1,84 @@
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use crate::distribution::Distribution2D;
+use crate::image_data::ImageData;
+use crate::prelude::*;
+
+// ----- Aperture -----
+
+/// The shape of a thin lens aperture, which out-of-focus highlights (bokeh) take on.
+#[derive(Clone)]
+pub enum Aperture {
+    Circular,
+    Polygon { blades: u32, rotation: f64 }, // Regular polygon of straight blades, rotated in degrees
+    Image(Arc<ApertureImage>),
+}
+
+/// A custom aperture mask, e.g. a star or heart cut-out, open where the image is bright.
+pub struct ApertureImage {
+    image: ImageData,
+    distribution: Distribution2D, // Luminance over the image, for sampling the open area
+}
+
+impl Aperture {
+    /// Constructor for an aperture of `blades` straight blades (at least 3), rotated in degrees.
+    pub fn polygon(blades: u32, rotation: f64) -> Self {
+        Aperture::Polygon { blades: blades.max(3), rotation }
+    }
+
+    /// Constructor for an aperture cut out like an image, read as grayscale transmission.
+    pub fn image(filename: &str) -> Self {
+        Aperture::Image(Arc::new(ApertureImage::new(filename)))
+    }
+
+    /// Sample a point on the aperture, uniformly over its open area, within the unit disk
+    /// (or the unit square for image apertures).
+    pub fn sample(&self) -> Vec3 {
+        match self {
+            Aperture::Circular => Vec3::random_in_unit_circle(),
+            Aperture::Polygon { blades, rotation } => {
+                // Pick one of the equal triangles fanning out from the center, then a point in it
+                let blade = random_usize(0, *blades as usize - 1) as f64;
+                let step = 2.0 * PI / *blades as f64;
+                let angle0 = rotation.to_radians() + blade * step;
+                let (a, b) = (Vec3::new(angle0.cos(), angle0.sin(), 0.0), Vec3::new((angle0 + step).cos(), (angle0 + step).sin(), 0.0));
+
+                let (mut r1, mut r2) = (random_f64(), random_f64());
+                if r1 + r2 > 1.0 {
+                    (r1, r2) = (1.0 - r1, 1.0 - r2);
+                }
+                r1 * a + r2 * b
+            }
+            Aperture::Image(image) => image.sample(),
+        }
+    }
+}
+
+impl ApertureImage {
+    /// Load an aperture mask from an image file, searched for like texture images.
+    pub fn new(filename: &str) -> Self {
+        let image = ImageData::new(filename);
+        let distribution = if image.is_empty() {
+            Distribution2D::new(&[1.0], 1, 1)
+        } else {
+            let (width, height) = (image.width() as usize, image.height() as usize);
+            let func: Vec<f64> = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| image.pixel_data(x as u32, y as u32).luminance().max(0.0))
+                .collect();
+            Distribution2D::new(&func, width, height)
+        };
+        Self { image, distribution }
+    }
+
+    /// Sample a point in the unit square with density proportional to the mask's brightness,
+    /// with the image upright as seen looking into the lens.
+    fn sample(&self) -> Vec3 {
+        let ((u, v), _) = self.distribution.sample(random_f64(), random_f64());
+        if self.image.is_empty() {
+            return Vec3::random_in_unit_circle();
+        }
+        Vec3::new(2.0 * u - 1.0, 1.0 - 2.0 * v, 0.0)
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::environment::EnvironmentMap;
+use crate::image_data::ImageData;
+use crate::prelude::*;
+
+// ----- Enum for different background types -----
+
+/// What a ray sees when it escapes the scene.
+#[derive(Clone)]
+pub enum Background {
+    Solid(Color),
+    Gradient(Gradient),
+    Plate(ImagePlate),
+    Environment(Arc<EnvironmentMap>),
+}
+
+impl Background {
+    /// Radiance seen along `direction`. `screen_uv` is where the direction lands on the camera's
+    /// image plane, in [0,1]^2 from the top-left, used by image plates.
+    pub fn radiance(&self, direction: &Vec3, screen_uv: (f64, f64)) -> Color {
+        match self {
+            Background::Solid(color) => *color,
+            Background::Gradient(gradient) => gradient.radiance(direction),
+            Background::Plate(plate) => plate.radiance(screen_uv),
+            Background::Environment(environment) => environment.radiance(direction),
+        }
+    }
+
+    /// The environment map to importance sample for lighting, if any.
+    pub fn environment_map(&self) -> Option<&Arc<EnvironmentMap>> {
+        match self {
+            Background::Environment(environment) => Some(environment),
+            _ => None,
+        }
+    }
+
+    // Convenience constructors
+
+    /// Create a constant color background.
+    pub fn solid(color: Color) -> Self {
+        Self::Solid(color)
+    }
+    /// Create a vertical gradient from `bottom` (straight down) to `top` (straight up).
+    pub fn gradient(bottom: Color, top: Color) -> Self {
+        Self::Gradient(Gradient::new(bottom, top))
+    }
+    /// Create an image plate filling the camera frame from the given image file.
+    pub fn plate(filename: &str) -> Self {
+        Self::Plate(ImagePlate::new(filename))
+    }
+    /// Create a background from an environment map, shared with light sampling.
+    pub fn environment(environment: EnvironmentMap) -> Self {
+        Self::Environment(Arc::new(environment))
+    }
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Self {
+        Background::Solid(color)
+    }
+}
+
+// ----- Gradient Background -----
+
+/// A vertical two-color gradient, blended by the direction's height.
+#[derive(Clone)]
+pub struct Gradient {
+    bottom: Color,
+    top: Color,
+}
+
+impl Gradient {
+    /// Constructor from the colors straight down and straight up.
+    pub fn new(bottom: Color, top: Color) -> Self {
+        Self { bottom, top }
+    }
+
+    #[inline]
+    fn radiance(&self, direction: &Vec3) -> Color {
+        let t = 0.5 * (Vec3::unit_vector(direction).y() + 1.0);
+        (1.0 - t) * self.bottom + t * self.top
+    }
+}
+
+// ----- Image Plate Background -----
+
+/// A backplate image stretched over the camera frame, regardless of the ray's direction.
+/// Directions outside the frame repeat the nearest edge pixel.
+#[derive(Clone)]
+pub struct ImagePlate {
+    image: ImageData,
+}
+
+impl ImagePlate {
+    /// Constructor from an image file, searched for like image textures.
+    pub fn new(filename: &str) -> Self {
+        Self { image: ImageData::new(filename) }
+    }
+
+    #[inline]
+    fn radiance(&self, (u, v): (f64, f64)) -> Color {
+        let x = (u.clamp(0.0, 1.0) * self.image.width() as f64) as u32;
+        let y = (v.clamp(0.0, 1.0) * self.image.height() as f64) as u32;
+        self.image.pixel_data(x, y) // Clamped to the image by pixel_data
+    }
+}
@@ -1,7 +1,16 @@
-use crate::material::ScatterRecord;
+use crate::animation::Sequence;
+use crate::aperture::Aperture;
+use crate::background::Background;
+use crate::lens::{FocusedLens, LensSystem};
+use crate::light::Light;
+use crate::material::{Material, ScatterRecord};
+use crate::medium::{Medium, MediumSample, MediumStack};
 use crate::prelude::*;
 use crate::hittable::{Hittable, HitRecord};
 use crate::pdf::*;
+use crate::physical_camera::PhysicalCamera;
+use crate::projection::{Projection, Stereo, StereoLayout};
+use crate::onb::ONB;
 
 // External crates
 use indicatif::{ProgressBar, ProgressStyle};
@@ -16,15 +25,31 @@ pub struct Camera {
     pub image_width: u32,       // Rendered image width in pixel count
     pub samples_per_pixel: u32, // Number of samples per pixel for anti-aliasing
     pub max_depth: u32,         // Maximum ray bounce depth
-    pub background: Color,      // Background color
+    pub background: Background, // What camera rays see when they escape the scene
+    pub lighting: Option<Background>, // What lighting (bounced) rays see, or None to use the background
+    pub lights: Vec<Light>,     // Delta lights (point, spot, directional), reached only by explicit sampling
+    pub medium: Option<Arc<Medium>>, // Medium the camera sits in, e.g. fog filling a room, or None for vacuum
 
     pub v_fov: f64,             // Vertical view angle (field of view)
     pub look_from: Point3,      // Point camera is looking from
     pub look_at: Point3,        // Point camera is looking at
     pub v_up: Vec3,             // "Up" direction for the camera
+    pub projection: Projection, // How the image maps to directions: perspective, orthographic, fisheye or panoramic
+    pub stereo: Option<Stereo>, // Render both eyes into one image, or None for a single view
 
     pub aperture_angle: f64,    // Variation angle of rays through each pixel
     pub focus_dist: f64,        // Distance from camera lookfrom point to plane of perfect focus
+    pub lens: Option<Arc<LensSystem>>, // Lens prescription traced for perspective views instead of a thin lens, or None
+    pub aperture: Aperture,     // Shape of the thin lens aperture, which bokeh takes on
+    pub cat_eye: f64,           // Shift of the lens barrel clipping the aperture at the image corners, in aperture radii (0 for none)
+    pub tilt: (f64, f64),       // Focal plane tilt in degrees about the horizontal and vertical image axes; positive tilts the top and right away
+    pub shift: (f64, f64),      // Lens shift right and up as a fraction of the image size, keeping verticals parallel
+
+    pub shutter_open: f64,      // Scene time the shutter opens; moving spheres travel over [0,1] and hold outside it
+    pub shutter_close: f64,     // Scene time the shutter closes, equal to open for no motion blur
+    pub physical: Option<PhysicalCamera>, // Focal length, f-stop, shutter speed and ISO setting the view, blur and exposure, or None.
+                                          // Takes precedence over the v_fov, aperture_angle and shutter_close set here or by a camera
+                                          // path or sequence shutter angle. With a lens, the lens sets the view and its stop the f-number.
 
     pub scene_name: String,     // Name of the scene for output file naming
     pub append_data: bool,      // Whether to append scene characteristics to output filename
@@ -42,6 +67,10 @@ pub struct Camera {
     w: Vec3,                    // Camera coordinate system basis vector w
     aperture_disk_u: Vec3,      // Aperture disk horizontal radius
     aperture_disk_v: Vec3,      // Aperture disk vertical radius
+    half_width_tan: f64,        // Half the viewport width over the focus distance
+    half_height_tan: f64,       // Half the viewport height over the focus distance
+    focused_lens: Option<FocusedLens>, // The lens focused at the focus distance, if any
+    exposure: f64,              // Scale from scene radiance to display values
 }
 
 impl Camera {
@@ -52,12 +81,85 @@ impl Camera {
         let world: Hittable = world.into();
 
         self.initialize();
+        self.print_exposure();
 
         let start_time = Instant::now();
+        let raw_img = self.render_pixels(&world, sample_target.as_ref());
 
-        let width = self.image_width;
-        let height = self.image_height;
+        // Calculate elapsed time
+        let elapsed = start_time.elapsed();
+        let minutes = elapsed.as_secs() / 60;
+        let seconds = elapsed.as_secs() % 60;
+        let time_str = format!("{}m{}s", minutes, seconds);
+
+        // Generate output filename with dimensions and characteristics
+        if self.scene_name.is_empty() { self.scene_name = "render".to_string(); }
+        let mut filename = format!(
+            "{}_{}x{}_{}spp_{}depth_{}.png",
+            self.scene_name,
+            self.output_size().0,
+            self.output_size().1,
+            self.samples_per_pixel,
+            self.max_depth,
+            time_str
+        );
+        if !self.append_data { filename = format!("{}.png", self.scene_name); }
+
+        self.save(raw_img, &filename);
+    }
+
+    /// Render the frames of a sequence of a static world, numbered in the sequence's output directory.
+    /// The world, with its BVH, is built once and reused for every frame; objects move through
+    /// animated transforms and deforming meshes keyed in scene time.
+    pub fn render_sequence(&mut self, world: impl Into<Hittable>, sample_target: Option<Arc<Hittable>>, sequence: &Sequence) {
+        let world: Hittable = world.into();
+        for frame in 0..sequence.frames {
+            self.render_frame(&world, sample_target.as_ref(), sequence, frame);
+        }
+    }
+
+    /// Render the frames of a sequence whose world changes in ways transforms cannot express,
+    /// building it for each frame from the frame number and its scene time.
+    pub fn render_sequence_with(&mut self, sequence: &Sequence, mut scene: impl FnMut(u32, f64) -> (Hittable, Option<Arc<Hittable>>)) {
+        for frame in 0..sequence.frames {
+            let (world, sample_target) = scene(frame, sequence.frame_time(frame));
+            self.render_frame(&world, sample_target.as_ref(), sequence, frame);
+        }
+    }
+
+    // ----- Private -----
+
+    /// Render one frame of a sequence: set the shutter, place the camera, and save the image.
+    /// The camera holds its pose from the middle of the shutter interval.
+    fn render_frame(&mut self, world: &Hittable, sample_target: Option<&Arc<Hittable>>, sequence: &Sequence, frame: u32) {
+        let (open, close) = sequence.shutter(frame);
+        self.shutter_open = open;
+        self.shutter_close = close;
+        if let Some(path) = &sequence.camera_path {
+            let pose = path.at(0.5 * (open + close));
+            self.look_from = pose.look_from;
+            self.look_at = pose.look_at;
+            self.v_fov = pose.v_fov;
+            self.focus_dist = pose.focus_dist;
+        }
+
+        self.initialize();
+        if frame == 0 { self.print_exposure(); }
+        eprintln!("Frame {}/{}", frame + 1, sequence.frames);
+        let raw_img = self.render_pixels(world, sample_target);
+
+        if self.scene_name.is_empty() { self.scene_name = "render".to_string(); }
+        std::fs::create_dir_all(&sequence.output_dir)
+            .unwrap_or_else(|e| panic!("Failed to create {}: {}", sequence.output_dir, e));
+        let filename = format!("{}/{}_{:04}.png", sequence.output_dir, self.scene_name, frame);
+        self.save(raw_img, &filename);
+    }
+
+    /// Trace every pixel of the image, returning its raw RGB bytes.
+    fn render_pixels(&self, world: &Hittable, sample_target: Option<&Arc<Hittable>>) -> Vec<u8> {
+        let (width, height) = self.output_size();
         let max_depth = self.max_depth;
+        let media = MediumStack::new(self.medium.clone());
 
         // Progress bar by row
         let pb = Self::create_progress_bar(height as u64);
@@ -73,19 +175,22 @@ impl Camera {
                 for i in 0..(width as usize) {
                     let mut pixel_color = Color::default();
                     let mut rec = HitRecord::new();
+                    let (eye, eye_i, eye_j) = self.eye_pixel(i as u32, j as u32);
                     for s_j in 0..self.sqrt_spp {
                         for s_i in 0..self.sqrt_spp {
-                            let r: Ray = self.get_ray(i as u32, j as u32, s_i, s_j);
-                            pixel_color += self.ray_color(
+                            let Some((r, weight)) = self.get_ray(eye_i, eye_j, s_i, s_j, eye) else { continue; };
+                            pixel_color += weight * self.ray_color(
                                 &r, 
                                 max_depth, 
-                                &world, 
-                                sample_target.as_ref(), // pass Option<&Arc<Hittable>>
-                                &mut rec
+                                world, 
+                                sample_target, // pass Option<&Arc<Hittable>>
+                                true,
+                                &mut rec,
+                                &media
                             );
                         }
                     }
-                    pixel_color *= self.pixel_samples_scaled;
+                    pixel_color *= self.pixel_samples_scaled * self.exposure;
                     let rgb = pixel_color.as_rgb();
                     row[i * 3] = rgb[0];
                     row[i * 3 + 1] = rgb[1];
@@ -95,36 +200,20 @@ impl Camera {
             });
 
         pb.finish_with_message("Render complete!");
+        raw_img
+    }
 
-        // Calculate elapsed time
-        let elapsed = start_time.elapsed();
-        let minutes = elapsed.as_secs() / 60;
-        let seconds = elapsed.as_secs() % 60;
-        let time_str = format!("{}m{}s", minutes, seconds);
-
-        // Generate output filename with dimensions and characteristics
-        if self.scene_name.is_empty() { self.scene_name = "render".to_string(); }
-        let mut filename = format!(
-            "{}_{}x{}_{}spp_{}depth_{}.png",
-            self.scene_name,
-            width,
-            height,
-            self.samples_per_pixel,
-            max_depth,
-            time_str
-        );
-        if !self.append_data { filename = format!("{}.png", self.scene_name); }
-
+    /// Save raw RGB bytes as an image.
+    fn save(&self, raw_img: Vec<u8>, filename: &str) {
         // Build the image and save
         // Default colorspace of an ImageBuffer is sRGB
+        let (width, height) = self.output_size();
         let img = image::RgbImage::from_raw(width, height, raw_img)
             .expect("Buffer size mismatch");
-        img.save(&filename).expect(&format!("Failed to save {}", filename));
+        img.save(filename).expect(&format!("Failed to save {}", filename));
         eprintln!("Image saved to {}", filename);
     }
 
-    // ----- Private -----
-
     /// Create and configure a progress bar.
     fn create_progress_bar(total: u64) -> ProgressBar {
         let pb = ProgressBar::new(total);
@@ -148,11 +237,29 @@ impl Camera {
 
         self.center = self.look_from;
 
+        // Physical settings replace the field of view, aperture and shutter interval, unless a lens
+        // prescription sets the view and aperture itself
+        if let Some(physical) = self.physical {
+            if self.lens.is_none() {
+                self.v_fov = physical.v_fov(self.focus_dist, self.image_width as f64 / self.image_height as f64);
+                self.aperture_angle = physical.aperture_angle(self.focus_dist);
+            }
+            self.shutter_close = self.shutter_open + physical.shutter_speed;
+        }
+
         // Determine viewport dimensions
         let theta = self.v_fov.to_radians();
         let h = (theta / 2.0).tan();
-        let viewport_height = 2.0 * h * self.focus_dist;
-        let viewport_width = viewport_height * (self.image_width as f64 / self.image_height as f64);
+        let mut viewport_height = 2.0 * h * self.focus_dist;
+        let mut viewport_width = viewport_height * (self.image_width as f64 / self.image_height as f64);
+        self.half_width_tan = 0.5 * viewport_width / self.focus_dist;
+        self.half_height_tan = 0.5 * viewport_height / self.focus_dist;
+
+        // Orthographic views have a fixed size instead of widening with distance
+        if let Projection::Orthographic { view_height } = self.projection {
+            viewport_height = view_height;
+            viewport_width = viewport_height * (self.image_width as f64 / self.image_height as f64);
+        }
 
         // Calculate the u,v,w unit basis vectors for the camera coordinate frame.
         self.w = Vec3::unit_vector(&(self.look_from - self.look_at));
@@ -167,32 +274,129 @@ impl Camera {
         self.pixel_delta_u = viewport_u / self.image_width as f64;
         self.pixel_delta_v = viewport_v / self.image_height as f64;
 
-        // Calculate the location of the upper left pixel.
-        let viewport_upper_left: Point3 = self.center - (self.focus_dist * self.w) - viewport_u / 2.0 - viewport_v / 2.0;                           
+        // Calculate the location of the upper left pixel, moved by any lens shift.
+        let viewport_upper_left: Point3 = self.center - (self.focus_dist * self.w) - viewport_u / 2.0 - viewport_v / 2.0
+                                        + self.shift.0 * viewport_u - self.shift.1 * viewport_v;                           
         self.pixel00_loc = viewport_upper_left + 0.5 * (self.pixel_delta_u + self.pixel_delta_v);
 
         // Calculate the camera aperture disk basis vectors.
         let aperture_radius = self.focus_dist * (self.aperture_angle.to_radians() / 2.0).tan();
         self.aperture_disk_u = self.u * aperture_radius;
         self.aperture_disk_v = self.v * aperture_radius;
+
+        // Focus the lens, replacing the field of view and aperture of perspective views
+        self.focused_lens = match (&self.lens, self.projection) {
+            (Some(lens), Projection::Perspective) => Some(lens.focus(self.focus_dist, self.image_width as f64 / self.image_height as f64)
+                .unwrap_or_else(|| panic!("Lens cannot focus at distance {}", self.focus_dist))),
+            _ => None,
+        };
+
+        // Exposure from the physical settings, at the f-number of the lens when there is one
+        self.exposure = self.exposure_settings().map_or(1.0, |physical| physical.exposure_scale());
     }
 
-    /// Get a ray from the camera through pixel (i,j) and subpixel sample (s_i, s_j).
-    fn get_ray(&self, i: u32, j: u32, s_i: u32, s_j: u32) -> Ray {
-        // Construct a camera ray originating from the defocus disk and directed at a randomly
-        // sampled point around the pixel location i, j for stratified sample square s_i, s_j.
+    /// The physical settings exposure is computed from, with the f-stop of the focused lens if any.
+    fn exposure_settings(&self) -> Option<PhysicalCamera> {
+        let physical = self.physical?;
+        let f_stop = self.focused_lens.as_ref().and_then(FocusedLens::f_number).unwrap_or(physical.f_stop);
+        Some(PhysicalCamera { f_stop, ..physical })
+    }
+
+    /// Report the exposure of physical settings, once per render.
+    fn print_exposure(&self) {
+        let Some(physical) = self.exposure_settings() else { return };
+        if self.focused_lens.is_some() {
+            eprintln!("Exposure: EV100 {:.1} at the lens's f/{:.1}", physical.ev100(), physical.f_stop);
+        } else {
+            eprintln!("Exposure: EV100 {:.1}, vertical FOV {:.1} degrees", physical.ev100(), self.v_fov);
+        }
+    }
 
+    /// Get a ray from the camera through pixel (i,j) and subpixel sample (s_i, s_j), seen from an
+    /// eye (-1 left, 1 right, 0 for a single view), with the weight of its contribution.
+    /// None where the projection shows nothing or the lens blocks the ray.
+    fn get_ray(&self, i: u32, j: u32, s_i: u32, s_j: u32, eye: f64) -> Option<(Ray, f64)> {
         let offset = self.sample_square_stratified(s_i, s_j);
-        let pixel_sample = self.pixel00_loc
-                         + (i as f64 + offset.x()) * self.pixel_delta_u
-                         + (j as f64 + offset.y()) * self.pixel_delta_v;  
+        let ray_time = random_f64_range(self.shutter_open, self.shutter_close);
+        let eye_offset = 0.5 * eye * self.stereo.map_or(0.0, |stereo| stereo.eye_separation);
+
+        // Trace through the lens elements, then from lens space (mm, +z toward the scene) to the world
+        if let Some(lens) = &self.focused_lens {
+            let s = (i as f64 + 0.5 + offset.x()) / self.image_width as f64;
+            let t = (j as f64 + 0.5 + offset.y()) / self.image_height as f64;
+            let (origin, direction, weight) = lens.sample_ray(s, t)?;
+            let to_world = |v: Vec3| v.x() * self.u + v.y() * self.v - v.z() * self.w;
+            let ray_origin = self.center + eye_offset * self.u + lens.units_per_mm() * to_world(origin);
+            return Some((Ray::new_with_time(ray_origin, to_world(direction), ray_time), weight));
+        }
+
+        match self.projection {
+            Projection::Perspective | Projection::Orthographic { .. } => {
+                // Construct a camera ray originating from the defocus disk and directed at a randomly
+                // sampled point around the pixel location i, j for stratified sample square s_i, s_j.
+                let pixel_sample = self.pixel00_loc
+                                 + (i as f64 + offset.x()) * self.pixel_delta_u
+                                 + (j as f64 + offset.y()) * self.pixel_delta_v;
+
+                // Perspective rays leave the camera center, orthographic ones the plane through it.
+                // Both eyes aim at the same point, so stereo views converge at the focus distance.
+                let center = match self.projection {
+                    Projection::Orthographic { .. } => pixel_sample + self.focus_dist * self.w,
+                    _ => self.center,
+                } + eye_offset * self.u;
+
+                // Use ideal or realistic aperture based on aperture setting
+                if self.aperture_angle <= 0.0 {
+                    return Some((Ray::new_with_time(center, pixel_sample - center, ray_time), 1.0));
+                }
+                let s = (i as f64 + 0.5 + offset.x()) / self.image_width as f64;
+                let t = (j as f64 + 0.5 + offset.y()) / self.image_height as f64;
+                let ray_origin = self.aperture_disk_sample(&center, s, t)?;
+                let focus_point = self.focus_point(&center, &pixel_sample);
+                Some((Ray::new_with_time(ray_origin, focus_point - ray_origin, ray_time), 1.0))
+            }
+            Projection::Fisheye { .. } | Projection::Equirectangular => {
+                let s = (i as f64 + 0.5 + offset.x()) / self.image_width as f64;
+                let t = (j as f64 + 0.5 + offset.y()) / self.image_height as f64;
+                let local = self.projection.direction(s, t, self.image_width, self.image_height)?;
+                let direction = local.x() * self.u + local.y() * self.v + local.z() * self.w;
+
+                // Omni-directional stereo: the eyes sit across each viewing direction, closing in
+                // toward straight up and down, where the offset fades with cos(latitude).
+                let center = self.center + eye_offset * Vec3::cross(&direction, &self.v);
+
+                // Thin lens facing the viewing direction, focused at the focus distance along it
+                let ray_origin = if self.aperture_angle <= 0.0 {
+                    center
+                } else {
+                    let lens = ONB::new(&direction);
+                    let p = self.aperture.sample() * self.aperture_disk_u.length();
+                    center + p.x() * lens.u() + p.y() * lens.v()
+                };
+                let focus_point = center + self.focus_dist * direction;
+                Some((Ray::new_with_time(ray_origin, focus_point - ray_origin, ray_time), 1.0))
+            }
+        }
+    }
 
-        // Use ideal or realistic aperture based on aperture setting
-        let ray_origin = if self.aperture_angle <= 0.0 { self.center } else { self.aperture_disk_sample() }; 
-        let ray_direction = pixel_sample - ray_origin;
-        let ray_time = random_f64();
+    /// Size of the output image, with room for both eyes in stereo.
+    fn output_size(&self) -> (u32, u32) {
+        match self.stereo.map(|stereo| stereo.layout) {
+            None => (self.image_width, self.image_height),
+            Some(StereoLayout::SideBySide) => (2 * self.image_width, self.image_height),
+            Some(StereoLayout::TopBottom) => (self.image_width, 2 * self.image_height),
+        }
+    }
 
-        Ray::new_with_time(ray_origin, ray_direction, ray_time)
+    /// The eye (-1 left, 1 right, 0 for a single view) and its pixel for an output image pixel.
+    fn eye_pixel(&self, i: u32, j: u32) -> (f64, u32, u32) {
+        match self.stereo.map(|stereo| stereo.layout) {
+            None => (0.0, i, j),
+            Some(StereoLayout::SideBySide) if i < self.image_width => (-1.0, i, j),
+            Some(StereoLayout::SideBySide) => (1.0, i - self.image_width, j),
+            Some(StereoLayout::TopBottom) if j < self.image_height => (-1.0, i, j),
+            Some(StereoLayout::TopBottom) => (1.0, i, j - self.image_height),
+        }
     }
 
     /// Returns a stratified random point in the unit square sub-pixel specified by grid
@@ -213,27 +417,111 @@ impl Camera {
         Vec3::new(random_f64() - 0.5, random_f64() - 0.5, 0.0)
     }
 
-    /// Returns a random point in the camera aperture disk.
-    fn aperture_disk_sample(&self) -> Point3 {
-        let p = Vec3::random_in_unit_circle();
-        self.center + (p.x() * self.aperture_disk_u) + (p.y() * self.aperture_disk_v)
+    /// Returns a random point in the camera aperture around the given center, for a ray toward the
+    /// image point (s, t) in [0,1]^2 from the top-left. None if the lens barrel blocks it (cat's eye).
+    fn aperture_disk_sample(&self, center: &Point3, s: f64, t: f64) -> Option<Point3> {
+        let p = self.aperture.sample();
+
+        // Off-axis, the barrel's circle slides toward the image edge and clips the aperture to a lens shape
+        if self.cat_eye > 0.0 {
+            let (width, height) = (self.image_width as f64, self.image_height as f64);
+            let diagonal = (width * width + height * height).sqrt();
+            let barrel = self.cat_eye * Vec3::new((2.0 * s - 1.0) * width / diagonal, (1.0 - 2.0 * t) * height / diagonal, 0.0);
+            if (p - barrel).length_squared() > 1.0 {
+                return None;
+            }
+        }
+
+        Some(*center + (p.x() * self.aperture_disk_u) + (p.y() * self.aperture_disk_v))
+    }
+
+    /// Where the ray from the lens center through a point on the image plane meets the plane of
+    /// perfect focus, which a tilted lens swings away from the image plane (Scheimpflug principle).
+    fn focus_point(&self, center: &Point3, pixel_sample: &Point3) -> Point3 {
+        if self.tilt == (0.0, 0.0) {
+            return *pixel_sample;
+        }
+
+        let normal = self.w + self.tilt.0.to_radians().tan() * self.v + self.tilt.1.to_radians().tan() * self.u;
+        let plane_point = self.center - self.focus_dist * self.w;
+        let direction = *pixel_sample - *center;
+        let denominator = Vec3::dot(&direction, &normal);
+        let t = if denominator.abs() > EPSILON { Vec3::dot(&(plane_point - *center), &normal) / denominator } else { 0.0 };
+        if t > 0.0 { *center + t * direction } else { *pixel_sample }
     } 
 
     /// Compute the color seen along a ray.
+    /// `camera_path` is true for camera rays and their chains of specular bounces, which see the
+    /// visible background rather than the lighting one. `media` holds the volumes the ray is inside.
     #[inline]
-    fn ray_color(&self, r: &Ray, depth: u32, world: &Hittable, sample_target: Option<&Arc<Hittable>>, rec: &mut HitRecord) -> Color { // TODO: change method declarations all over the place to separate input parameters onto separate lines for readability
+    #[allow(clippy::too_many_arguments)]
+    fn ray_color(&self, r: &Ray, depth: u32, world: &Hittable, sample_target: Option<&Arc<Hittable>>, camera_path: bool, rec: &mut HitRecord, media: &MediumStack) -> Color { // TODO: change method declarations all over the place to separate input parameters onto separate lines for readability
         // If we've exceeded the ray bounce limit, no more light is gathered.
         if depth <= 0 { return Color::zero(); }
 
-        // If ray hits nothing, return background color
-        if !world.hit(r, &Interval::new(0.001, f64::INFINITY), rec) {
-            return self.background;
+        let hit = world.hit(r, &Interval::new(0.001, f64::INFINITY), rec);
+
+        // Inside an enclosed medium, the ray may scatter before reaching the surface
+        let mut weight = Color::new(1.0, 1.0, 1.0);
+        let mut medium_color = Color::zero();
+        if let Some(medium) = media.medium() {
+            let speed = r.direction.length();
+            let distance = if hit { rec.t * speed } else { f64::INFINITY };
+            medium_color = medium.emitted(distance);
+            match medium.sample(distance) {
+                MediumSample::Scatter { distance, weight } => {
+                    rec.t = distance / speed;
+                    rec.point = r.at(rec.t);
+
+                    // Arbitrary normal/face; medium scattering doesn't depend on surface orientation.
+                    rec.normal = Vec3::new(1.0, 0.0, 0.0);
+                    rec.front_face = true;
+                    rec.material = Arc::clone(medium.phase_function());
+                    let weight = weight * world.medium_weight(r, &Interval::new(0.001, rec.t));
+                    return medium_color + weight * self.shade(r, depth, world, sample_target, camera_path, rec, media);
+                }
+                MediumSample::Pass { weight: pass_weight } => weight = pass_weight,
+            }
+            if weight.near_zero() {
+                return medium_color;
+            }
         }
 
+        // Chromatic media the ray passed through without colliding reweight its channels
+        weight = weight * world.medium_weight(r, &Interval::new(0.001, if hit { rec.t } else { f64::INFINITY }));
+
+        // If ray hits nothing, return what the background shows this kind of ray
+        if !hit {
+            let background = if camera_path { &self.background } else { self.lighting_background() };
+            return medium_color + weight * background.radiance(&r.direction, self.screen_uv(&r.direction));
+        }
+
+        medium_color + weight * self.shade(r, depth, world, sample_target, camera_path, rec, media)
+    }
+
+    /// Compute the light leaving a hit point, at a surface or a scattering event in a medium, back along the ray.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn shade(&self, r: &Ray, depth: u32, world: &Hittable, sample_target: Option<&Arc<Hittable>>, camera_path: bool, rec: &mut HitRecord, media: &MediumStack) -> Color {
         // Testing: return just the normal for debugging:
         #[cfg(feature = "normals")]
         { return 0.5 * rec.normal + Color::new(0.5, 0.5, 0.5); }
 
+        // Surfaces of enclosed volumes: skip those inside a higher priority volume, which are not
+        // real interfaces, and tell dielectrics the refractive index beyond the surface.
+        // Skipping only updates the volumes the ray is in, so it keeps the depth; the ray still
+        // advances past the surface.
+        let boundary = Arc::clone(&rec.material);
+        let interior = boundary.as_interior();
+        if let Some(interior) = interior
+            && !media.is_true_boundary(&boundary, interior.priority())
+        {
+            let next = Self::crossed_media(media, &boundary, rec);
+            let ray = Ray::new_with_time(rec.point, r.direction, r.time);
+            return self.ray_color(&ray, depth, world, sample_target, camera_path, rec, &next);
+        }
+        rec.outside_ior = media.outside_ior(interior.map(|_| &boundary));
+
         // TODO: Consider simplifying emitted() to just take rec, since rec.u, rec.v, rec.point are redundant
         // Emitted light from the hit point itself, before scattering
         let emitted_color = rec.material.emitted(r, rec, rec.u, rec.v, &rec.point);
@@ -246,21 +534,43 @@ impl Camera {
 
         // Specular path: follow the provided ray with no PDF work
         if srec.skip_pdf {
-            let spec_color = self.ray_color(&srec.skip_pdf_ray, depth - 1, world, sample_target, rec);
+            let next = match interior {
+                Some(_) if Vec3::dot(&srec.skip_pdf_ray.direction, &rec.normal) < 0.0 => Self::crossed_media(media, &boundary, rec),
+                _ => media.clone(),
+            };
+            let spec_color = self.ray_color(&srec.skip_pdf_ray, depth - 1, world, sample_target, camera_path, rec, &next);
             return emitted_color + srec.attenuation * spec_color;
         }
 
+        // Direct light from delta lights, which scattered rays can never hit.
+        // Sampled before recursing, since the recursive call reuses rec.
+        // Shadow rays leaving through an enclosing surface travel in the volume beyond it.
+        let beyond = interior.map(|_| Self::crossed_media(media, &boundary, rec));
+        let emitted_color = emitted_color + self.sample_lights(r, world, rec, &srec, media, beyond.as_ref());
+
         // Diffuse path: build mixture PDF or just use material PDF if no sample target
         let mat_pdf = srec
             .pdf_ptr
             .as_ref()
             .expect("scatter: pdf_ptr must be Some when skip_pdf is false")
             .clone();
+        // Light PDF from the sample target and the environment, whichever are present and reachable
+        // from the volume the ray is in, or will be in if it leaves through this surface
+        let reachable = beyond.as_ref().unwrap_or(media).lights_reachable();
+        let light_pdf = match (sample_target, self.lighting_background().environment_map()) {
+            _ if !reachable => None,
+            (Some(target), Some(environment)) => Some(PDF::mixture(
+                PDF::hittable(target.clone(), rec.point),
+                PDF::environment(environment.clone()),
+            )),
+            (Some(target), None) => Some(PDF::hittable(target.clone(), rec.point)),
+            (None, Some(environment)) => Some(PDF::environment(environment.clone())),
+            (None, None) => None,
+        };
         // Branch: with or without importance sampling
-        let (scattered, pdf_value) = if let Some(target) = sample_target {
+        let (scattered, pdf_value) = if let Some(light_pdf) = light_pdf {
             // Importance sampling: build mixture PDF from light + material PDFs
-            let importance_sampling_pdf = PDF::hittable(target.clone(), rec.point);
-            let mixture_pdf = PDF::mixture(importance_sampling_pdf, mat_pdf);
+            let mixture_pdf = PDF::mixture(light_pdf, mat_pdf);
             let s = Ray::new_with_time(rec.point, mixture_pdf.generate(), r.time);
             let v = mixture_pdf.value(&s.direction);
             (s, v)
@@ -275,17 +585,110 @@ impl Camera {
         if pdf_value <= 0.0 || !pdf_value.is_finite() {
             return emitted_color;
         }
-        let scattering_pdf = rec.material.scattering_pdf(r, rec, &scattered);
-        if scattering_pdf <= 0.0 || !scattering_pdf.is_finite() {
+        let scattering_bsdf = rec.material.scattering_bsdf(r, rec, &srec, &scattered);
+        if scattering_bsdf.near_zero() || !scattering_bsdf.is_finite() {
             return emitted_color;
         }
-        
-        let sample_color = self.ray_color(&scattered, depth - 1, world, sample_target, rec);
-        let scattered_color = (srec.attenuation * scattering_pdf * sample_color) / pdf_value;
+
+        // Diffuse transmission through an enclosing surface also changes the volume the ray is in
+        let next = match beyond {
+            Some(beyond) if Vec3::dot(&scattered.direction, &rec.normal) < 0.0 => beyond,
+            _ => media.clone(),
+        };
+        let sample_color = self.ray_color(&scattered, depth - 1, world, sample_target, false, rec, &next);
+        let scattered_color = (scattering_bsdf * sample_color) / pdf_value;
 
         emitted_color + scattered_color
     }
 
+    /// The volumes a ray is inside after crossing the surface of an enclosed volume at a hit.
+    fn crossed_media(media: &MediumStack, boundary: &Arc<Material>, rec: &HitRecord) -> MediumStack {
+        let Some(interior) = boundary.as_interior() else { return media.clone() };
+        if rec.front_face {
+            media.entered(boundary, interior.medium().cloned(), interior.priority(), boundary.refraction_index(rec))
+        } else {
+            media.exited(boundary)
+        }
+    }
+
+    /// The background seen by lighting rays.
+    #[inline]
+    fn lighting_background(&self) -> &Background {
+        self.lighting.as_ref().unwrap_or(&self.background)
+    }
+
+    /// Where a direction lands on the image plane, in [0,1]^2 from the top-left corner.
+    /// Directions behind the camera are pushed far outside the frame.
+    #[inline]
+    fn screen_uv(&self, direction: &Vec3) -> (f64, f64) {
+        let forward = Vec3::dot(direction, &-self.w).max(EPSILON);
+        let x = Vec3::dot(direction, &self.u) / (forward * self.half_width_tan);
+        let y = Vec3::dot(direction, &self.v) / (forward * self.half_height_tan);
+        (0.5 + 0.5 * x - self.shift.0, 0.5 - 0.5 * y + self.shift.1)
+    }
+
+    /// Sum the direct contribution of every delta light at a non-specular hit, tracing a shadow ray to each.
+    /// `beyond` holds the volumes on the far side of an enclosing surface, for shadow rays crossing it.
+    #[allow(clippy::too_many_arguments)]
+    fn sample_lights(&self, r: &Ray, world: &Hittable, rec: &HitRecord, srec: &ScatterRecord, media: &MediumStack, beyond: Option<&MediumStack>) -> Color {
+        if self.lights.is_empty() { return Color::zero(); }
+
+        let mut direct_color = Color::zero();
+        for light in &self.lights {
+            let Some(sample) = light.sample(&rec.point) else { continue };
+
+            // Lights cannot be reached from inside refracting volumes; otherwise surfaces block the
+            // shadow ray and media in between attenuate it
+            let shadow_media = match beyond {
+                Some(beyond) if Vec3::dot(&sample.direction, &rec.normal) < 0.0 => beyond,
+                _ => media,
+            };
+            if !shadow_media.lights_reachable() {
+                continue;
+            }
+            let shadow_ray = Ray::new_with_time(rec.point, sample.direction, r.time);
+            let transmittance = Self::shadow_transmittance(world, &shadow_ray, sample.distance, shadow_media);
+            if transmittance.near_zero() {
+                continue;
+            }
+
+            let scattering_bsdf = rec.material.scattering_bsdf(r, rec, srec, &shadow_ray);
+            if scattering_bsdf.is_finite() {
+                direct_color += transmittance * scattering_bsdf * sample.radiance;
+            }
+        }
+        direct_color
+    }
+
+    /// Fraction of light reaching along a shadow ray from a light `distance` away. Besides the
+    /// surfaces and convex media in between, the ray crosses invisible surfaces, such as the walls
+    /// of a foggy room, keeping track of the volumes it is in to apply their media piece by piece.
+    fn shadow_transmittance(world: &Hittable, ray: &Ray, distance: f64, media: &MediumStack) -> Color {
+        let mut transmittance = world.transmittance(ray, &Interval::new(0.001, distance - 0.001));
+        let mut media = media.clone();
+        let mut rec = HitRecord::new();
+        let (mut start, mut t_min) = (0.0, 0.001);
+        while !transmittance.near_zero() {
+            if !world.hit(ray, &Interval::new(t_min, distance - 0.001), &mut rec) {
+                if let Some(medium) = media.medium() {
+                    transmittance = transmittance * medium.transmittance(distance - start);
+                }
+                return transmittance;
+            }
+            t_min = rec.t + 0.001;
+
+            // Anything else the ray meets is a collision in a convex medium, already in the transmittance
+            let Some(tint) = rec.material.passthrough_tint() else { continue };
+            if let Some(medium) = media.medium() {
+                transmittance = transmittance * medium.transmittance(rec.t - start);
+            }
+            transmittance = transmittance * tint;
+            start = rec.t;
+            media = Self::crossed_media(&media, &rec.material, &rec);
+        }
+        Color::zero()
+    }
+
     /// Function to set camera parameters to a high-quality default.
     pub fn set_high_quality_settings(&mut self) {
         self.aspect_ratio = 16.0 / 9.0;
@@ -320,8 +723,12 @@ impl Default for Camera {
     /// - Look From: (0, 0, 0)
     /// - Look At: (0, 0, -1)
     /// - V Up: (0, 1, 0)
+    /// - Projection: perspective, mono
     /// - Aperture Angle: 0 (pinhole)
     /// - Focus Dist: 1
+    /// - Aperture: circular, no cat's eye, tilt or shift
+    /// - Shutter: open over [0, 1]
+    /// - Physical: none (unit exposure)
     /// - Append Data: true
     fn default() -> Self {
         Camera {
@@ -330,15 +737,29 @@ impl Default for Camera {
             image_width: 400,
             samples_per_pixel: 100,
             max_depth: 50,
-            background: Color::new(0.70, 0.80, 1.00), // Light blue sky
+            background: Background::solid(Color::new(0.70, 0.80, 1.00)), // Light blue sky
+            lighting: None,
+            lights: Vec::new(),
+            medium: None,
 
             v_fov: 90.0,
             look_from: Point3::new(0.0, 0.0, 0.0),
             look_at: Point3::new(0.0, 0.0, -1.0),
             v_up: Vec3::new(0.0, 1.0, 0.0),
+            projection: Projection::Perspective,
+            stereo: None,
 
             aperture_angle: 0.0,
             focus_dist: 1.0,
+            lens: None,
+            aperture: Aperture::Circular,
+            cat_eye: 0.0,
+            tilt: (0.0, 0.0),
+            shift: (0.0, 0.0),
+
+            shutter_open: 0.0,
+            shutter_close: 1.0,
+            physical: None,
 
             scene_name: String::new(),
             append_data: true,
@@ -358,6 +779,10 @@ impl Default for Camera {
             w: Vec3::zero(),
             aperture_disk_u: Vec3::zero(),
             aperture_disk_v: Vec3::zero(),
+            half_width_tan: 0.0,
+            half_height_tan: 0.0,
+            focused_lens: None,
+            exposure: 1.0,
         }
     }
 }
\ No newline at end of file
@@ -45,4 +45,14 @@ impl Color {
     pub fn rgb_bytes(r: u8, g: u8, b: u8) -> Color {
         Color::new(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
     }
+
+    /// Component-wise exponential, e.g. for Beer-Lambert transmittance `exp(-sigma * d)`.
+    pub fn exp(&self) -> Color {
+        Color::new(self.x().exp(), self.y().exp(), self.z().exp())
+    }
+
+    /// Relative luminance of a linear RGB color (Rec. 709 weights).
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.x() + 0.7152 * self.y() + 0.0722 * self.z()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+// Piecewise-constant distributions for importance sampling tabulated functions, such as the
+// luminance of an environment map. Sample coordinates and densities are over [0,1) (and [0,1)^2).
+
+// ----- 1D Distribution -----
+
+/// A piecewise-constant 1D distribution built from non-negative function values.
+#[derive(Clone)]
+pub struct Distribution1D {
+    func: Vec<f64>,
+    cdf: Vec<f64>, // n + 1 entries, cdf[0] = 0 and cdf[n] = 1
+    integral: f64, // Integral of the piecewise function over [0,1]
+}
+
+impl Distribution1D {
+    /// Build a distribution from function values. An all-zero function falls back to uniform sampling.
+    pub fn new(func: Vec<f64>) -> Self {
+        let func = if func.is_empty() { vec![0.0] } else { func };
+        let n = func.len();
+
+        let mut cdf = vec![0.0; n + 1];
+        for i in 0..n {
+            cdf[i + 1] = cdf[i] + func[i].max(0.0) / n as f64;
+        }
+        let integral = cdf[n];
+
+        if integral > 0.0 {
+            cdf.iter_mut().for_each(|c| *c /= integral);
+        } else {
+            cdf.iter_mut().enumerate().for_each(|(i, c)| *c = i as f64 / n as f64);
+        }
+
+        Self { func, cdf, integral }
+    }
+
+    /// Number of pieces in the distribution.
+    pub fn count(&self) -> usize {
+        self.func.len()
+    }
+
+    /// Integral of the function, used as the normalization constant.
+    pub fn integral(&self) -> f64 {
+        self.integral
+    }
+
+    /// Map a uniform sample `u` in [0,1) to a continuous sample in [0,1).
+    /// Returns the sample, its density, and the index of the piece it falls in.
+    pub fn sample_continuous(&self, u: f64) -> (f64, f64, usize) {
+        // Last cdf entry that is <= u
+        let offset = (self.cdf.partition_point(|&c| c <= u).max(1) - 1).min(self.count() - 1);
+
+        let mut du = u - self.cdf[offset];
+        let width = self.cdf[offset + 1] - self.cdf[offset];
+        if width > 0.0 {
+            du /= width;
+        }
+
+        let x = ((offset as f64 + du) / self.count() as f64).min(1.0 - f64::EPSILON);
+        (x, self.pdf_at(offset), offset)
+    }
+
+    /// Density of the piece at `index`.
+    #[inline]
+    pub fn pdf_at(&self, index: usize) -> f64 {
+        if self.integral > 0.0 { self.func[index].max(0.0) / self.integral } else { 1.0 }
+    }
+
+    /// Index of the piece containing the continuous coordinate `x` in [0,1).
+    #[inline]
+    pub fn index_of(&self, x: f64) -> usize {
+        ((x * self.count() as f64) as usize).min(self.count() - 1)
+    }
+}
+
+// ----- 2D Distribution -----
+
+/// A piecewise-constant 2D distribution, sampled by picking a row from the marginal and then
+/// a column from that row's conditional distribution.
+#[derive(Clone)]
+pub struct Distribution2D {
+    conditional: Vec<Distribution1D>, // One distribution over u per row
+    marginal: Distribution1D,         // Distribution over rows v
+}
+
+impl Distribution2D {
+    /// Build from row-major function values with `nu` columns and `nv` rows.
+    pub fn new(func: &[f64], nu: usize, nv: usize) -> Self {
+        let conditional: Vec<Distribution1D> = (0..nv)
+            .map(|v| Distribution1D::new(func[v * nu..(v + 1) * nu].to_vec()))
+            .collect();
+        let marginal = Distribution1D::new(conditional.iter().map(|d| d.integral()).collect());
+        Self { conditional, marginal }
+    }
+
+    /// Map two uniform samples to a point (u, v) in [0,1)^2, returning the point and its density.
+    pub fn sample(&self, u0: f64, u1: f64) -> ((f64, f64), f64) {
+        let (v, pdf_v, row) = self.marginal.sample_continuous(u1);
+        let (u, pdf_u, _) = self.conditional[row].sample_continuous(u0);
+        ((u, v), pdf_u * pdf_v)
+    }
+
+    /// Density of the point (u, v) in [0,1)^2.
+    pub fn pdf(&self, u: f64, v: f64) -> f64 {
+        let row = self.marginal.index_of(v);
+        let column = self.conditional[row].index_of(u);
+        self.conditional[row].pdf_at(column) * self.marginal.pdf_at(row)
+    }
+}
@@ -0,0 +1,150 @@
+use std::f64::consts::PI;
+
+use crate::distribution::Distribution2D;
+use crate::image_data::ImageData;
+use crate::prelude::*;
+
+/// An environment light surrounding the scene, from an equirectangular (latitude-longitude) image.
+/// Usually an HDR image (.hdr/.exr), importance sampled by luminance so small bright features
+/// such as the sun are found by light sampling.
+#[derive(Clone)]
+pub struct EnvironmentMap {
+    image: ImageData,
+    intensity: f64,               // Scale applied to the image radiance
+    sin_rotation: f64,            // Rotation about the +y axis
+    cos_rotation: f64,
+    distribution: Distribution2D, // Luminance * sin(theta) over the image, for sampling directions
+}
+
+impl EnvironmentMap {
+    /// Load an equirectangular environment image. The top row of the image is straight up (+y).
+    pub fn new(filename: &str) -> Self {
+        Self::from_image(ImageData::new(filename))
+    }
+
+    /// Create an environment from an equirectangular image already in memory.
+    pub fn from_image(image: ImageData) -> Self {
+        let distribution = Self::build_distribution(&image);
+        Self {
+            image,
+            intensity: 1.0,
+            sin_rotation: 0.0,
+            cos_rotation: 1.0,
+            distribution,
+        }
+    }
+
+    /// Bake an environment of the given resolution from a radiance function of direction,
+    /// e.g. a procedural sky, evaluated at each texel center.
+    pub fn from_fn(width: u32, height: u32, radiance: impl Fn(&Vec3) -> Color) -> Self {
+        let mut data = Vec::with_capacity((width * height * 3) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let u = (x as f64 + 0.5) / width as f64;
+                let v = (y as f64 + 0.5) / height as f64;
+                let color = radiance(&equirect_direction(u, v));
+                data.extend([color.x() as f32, color.y() as f32, color.z() as f32]);
+            }
+        }
+
+        Self::from_image(ImageData::from_linear(width, height, data))
+    }
+
+    /// Rotate the environment about the vertical axis by the given angle in degrees.
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        let radians = degrees.to_radians();
+        self.sin_rotation = radians.sin();
+        self.cos_rotation = radians.cos();
+        self
+    }
+
+    /// Set the intensity scale applied to the image.
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity.max(0.0);
+        self
+    }
+
+    /// Radiance arriving from the environment along the given direction (pointing away from the scene).
+    pub fn radiance(&self, direction: &Vec3) -> Color {
+        let (u, v) = self.direction_to_uv(direction);
+        let (x, y) = self.texel(u, v);
+        self.intensity * self.image.pixel_data(x, y)
+    }
+
+    /// Sample a direction towards the environment, proportional to its luminance.
+    pub fn sample(&self) -> Vec3 {
+        let ((u, v), _) = self.distribution.sample(random_f64(), random_f64());
+        self.uv_to_direction(u, v)
+    }
+
+    /// Solid-angle density of sampling the given direction with `sample`.
+    pub fn pdf(&self, direction: &Vec3) -> f64 {
+        let (u, v) = self.direction_to_uv(direction);
+        let sin_theta = (PI * v).sin();
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+        // Jacobian of the equirectangular mapping: dω = 2π² sin(theta) du dv
+        self.distribution.pdf(u, v) / (2.0 * PI * PI * sin_theta)
+    }
+
+    // ----- Private -----
+
+    /// Build the sampling distribution, weighting each texel by its solid angle.
+    fn build_distribution(image: &ImageData) -> Distribution2D {
+        if image.is_empty() {
+            return Distribution2D::new(&[1.0], 1, 1);
+        }
+
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let mut func = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let sin_theta = (PI * (y as f64 + 0.5) / height as f64).sin();
+            for x in 0..width {
+                func.push(image.pixel_data(x as u32, y as u32).luminance().max(0.0) * sin_theta);
+            }
+        }
+        Distribution2D::new(&func, width, height)
+    }
+
+    /// Pixel coordinates for the image coordinates (u, v) in [0,1)^2.
+    #[inline]
+    fn texel(&self, u: f64, v: f64) -> (u32, u32) {
+        let x = (u * self.image.width() as f64) as u32;
+        let y = (v * self.image.height() as f64) as u32;
+        (x, y) // Clamped by ImageData::pixel_data
+    }
+
+    /// Image coordinates of a world direction, with v = 0 straight up.
+    #[inline]
+    fn direction_to_uv(&self, direction: &Vec3) -> (f64, f64) {
+        let d = Vec3::unit_vector(direction);
+        // Undo the environment rotation to get the direction in image space
+        let x = self.cos_rotation * d.x() - self.sin_rotation * d.z();
+        let z = self.sin_rotation * d.x() + self.cos_rotation * d.z();
+
+        let theta = d.y().clamp(-1.0, 1.0).acos();
+        let phi = (-z).atan2(x) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
+
+    /// World direction for the image coordinates (u, v), the inverse of `direction_to_uv`.
+    #[inline]
+    fn uv_to_direction(&self, u: f64, v: f64) -> Vec3 {
+        let d = equirect_direction(u, v);
+        Vec3::new(
+            self.cos_rotation * d.x() + self.sin_rotation * d.z(),
+            d.y(),
+            -self.sin_rotation * d.x() + self.cos_rotation * d.z(),
+        )
+    }
+}
+
+/// Unrotated direction for the equirectangular image coordinates (u, v), with v = 0 straight up.
+#[inline]
+fn equirect_direction(u: f64, v: f64) -> Vec3 {
+    let theta = PI * v;
+    let phi = 2.0 * PI * u - PI;
+    let sin_theta = theta.sin();
+    Vec3::new(sin_theta * phi.cos(), theta.cos(), -sin_theta * phi.sin())
+}
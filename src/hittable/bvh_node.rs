@@ -1,6 +1,7 @@
 use super::{HitRecord, Hittable, HittableList, AABB};
 
 use crate::interval::Interval;
+use crate::color::Color;
 use crate::ray::Ray;
 
 use std::sync::Arc;
@@ -14,6 +15,7 @@ pub struct BVHNode {
     left: Arc<Hittable>,
     right: Arc<Hittable>,
     bounding_box: AABB,
+    chromatic_media: bool, // Whether the subtree holds chromatic media, which weight rays passing through
 }
 
 impl BVHNode {
@@ -64,6 +66,7 @@ impl BVHNode {
         
         Self {
             bounding_box: bbox,
+            chromatic_media: left.has_chromatic_media() || right.has_chromatic_media(),
             left,
             right,
         }
@@ -112,6 +115,43 @@ impl BVHNode {
         hit_left || hit_right
     }
 
+    /// Transmittance through both children, skipping the subtree if the ray misses it.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        if !self.bounding_box.hit(r, ray_t) {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+
+        // Check if leaf with single object
+        if Arc::ptr_eq(&self.left, &self.right) {
+            return self.left.transmittance(r, ray_t);
+        }
+
+        let left = self.left.transmittance(r, ray_t);
+        if left.near_zero() {
+            return Color::zero();
+        }
+        left * self.right.transmittance(r, ray_t)
+    }
+
+    /// Weight of passing through chromatic media in both children, skipping the subtree if it holds
+    /// none or the ray misses it.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        if !self.chromatic_media || !self.bounding_box.hit(r, ray_t) {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+
+        // Check if leaf with single object
+        if Arc::ptr_eq(&self.left, &self.right) {
+            return self.left.medium_weight(r, ray_t);
+        }
+        self.left.medium_weight(r, ray_t) * self.right.medium_weight(r, ray_t)
+    }
+
+    /// Whether the subtree holds chromatic media.
+    pub fn has_chromatic_media(&self) -> bool {
+        self.chromatic_media
+    }
+
     pub fn bounding_box(&self) -> &AABB {
         &self.bounding_box
     }
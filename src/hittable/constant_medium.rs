@@ -2,7 +2,8 @@ use super::{Hittable, HitRecord, AABB};
 
 use crate::interval::Interval;
 use crate::material::Material;
-use crate::prelude::{random_f64, EPSILON};
+use crate::medium::{Medium, MediumEvent};
+use crate::prelude::EPSILON;
 use crate::ray::Ray;
 use crate::texture::Texture;
 use crate::color::Color;
@@ -11,83 +12,139 @@ use crate::vec3::Vec3;
 use std::sync::Arc;
 
 /// A constant-desnity medium that scatters rays randomly, bounding by a convex hittable (e.g., a sphere or box).
+/// Absorption and scattering coefficients may differ per color channel, and the medium may glow.
+/// Collisions are tracked by the wrapped `Medium` with spectral tracking, so chromatic media stay
+/// unbiased per channel. The weight of passing through without a collision depends on which
+/// surface the ray reaches first, so the integrator applies it (see `medium_weight`).
 #[derive(Clone)]
 pub struct ConstantMedium {
     boundary: Arc<Hittable>,
-    neg_inv_density: f64,
-    phase_function: Arc<Material>,
+    medium: Medium,
+    scatter_event: Arc<Material>, // Phase function weighted by the scattering channels
+    absorb_event: Arc<Material>,  // Emission weighted by the absorbing channels
     bounding_box: AABB,
 }
 
 impl ConstantMedium {
     /// Constructor from solid color phase function.
     pub fn new(boundary: Arc<Hittable>, density: f64, albedo: &Color) -> Self {
-        let bbox = boundary.bounding_box().clone();
-        Self {
-            boundary,
-            neg_inv_density: -1.0 / density,
-            phase_function: Material::isotropic(*albedo),
-            bounding_box: bbox,
-        }
+        let medium = Medium::new(Color::zero(), density * Color::new(1.0, 1.0, 1.0)).with_phase_function(Material::isotropic(*albedo));
+        Self::from_medium(boundary, medium)
     }
 
     /// Constructor from texture and density.
     pub fn from_texture(boundary: Arc<Hittable>, density: f64, tex: Arc<Texture>) -> Self {
-        let bbox = boundary.bounding_box().clone();
+        let medium = Medium::new(Color::zero(), density * Color::new(1.0, 1.0, 1.0)).with_phase_function(Material::isotropic_tex(tex));
+        Self::from_medium(boundary, medium)
+    }
+
+    /// Constructor from absorption and scattering coefficients per color channel (per unit length).
+    /// The medium's color comes from the coefficients, so the phase function is white.
+    pub fn chromatic(boundary: Arc<Hittable>, sigma_a: Color, sigma_s: Color) -> Self {
+        Self::from_medium(boundary, Medium::new(sigma_a, sigma_s))
+    }
+
+    /// Constructor filling a convex boundary with a medium.
+    pub fn from_medium(boundary: Arc<Hittable>, medium: Medium) -> Self {
+        let bbox = *boundary.bounding_box();
+        let scatter_weight = medium.scatter_weight();
+        let scatter_event = if scatter_weight == Color::new(1.0, 1.0, 1.0) {
+            Arc::clone(medium.phase_function())
+        } else {
+            Material::tinted(Arc::clone(medium.phase_function()), scatter_weight)
+        };
         Self {
             boundary,
-            neg_inv_density: -1.0 / density,
-            phase_function: Material::isotropic_tex(tex),
+            scatter_event,
+            absorb_event: Material::diffuse_light(medium.emission() * medium.absorb_weight()),
+            medium,
             bounding_box: bbox,
         }
     }
 
-    /// Volume hit method. Sample a scattering event within the medium.
+    /// Replace the isotropic phase function, see `Medium::with_phase_function`.
+    pub fn with_phase_function(self, phase_function: Arc<Material>) -> Self {
+        Self::from_medium(self.boundary, self.medium.with_phase_function(phase_function))
+    }
+
+    /// Make the medium glow, e.g. fire or hot gas. Emission comes from the absorbing particles,
+    /// so a medium without absorption stays dark.
+    pub fn with_emission(self, emission: Color) -> Self {
+        Self::from_medium(self.boundary, self.medium.with_emission(emission))
+    }
+
+    /// Volume hit method. Track the ray through the medium between its entry and exit, and place
+    /// the first collision. Rays passing through are not hit.
     #[inline]
     pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
-        let mut rec1 = HitRecord::new();
-        let mut rec2 = HitRecord::new();
-
-        // Find entry/exit points on the convex boundary.
-        if !self.boundary.hit(r, &Interval::new(f64::NEG_INFINITY, f64::INFINITY), &mut rec1) {
+        let Some((t_enter, t_exit)) = boundary_span(&self.boundary, r, ray_t) else {
             return false;
-        }
-        if !self.boundary.hit(r, &Interval::new(rec1.t + EPSILON, f64::INFINITY), &mut rec2) {
-            return false;
-        }
+        };
 
-        // Clamp to the ray's active interval.
-        if rec1.t < ray_t.min { rec1.t = ray_t.min; }
-        if rec2.t > ray_t.max { rec2.t = ray_t.max; }
-        if rec1.t >= rec2.t { return false; }
-        if rec1.t < 0.0 { rec1.t = 0.0; }
-
-        // Sample a scattering distance inside the medium.
         let ray_length = r.direction.length();
-        let distance_inside_boundary = (rec2.t - rec1.t) * ray_length;
-        let hit_distance = self.neg_inv_density * (random_f64().max(EPSILON)).ln();
-
-        if hit_distance > distance_inside_boundary {
-            return false;
-        }
-
-        rec.t = rec1.t + hit_distance / ray_length;
+        let (distance, event) = match self.medium.track((t_exit - t_enter) * ray_length) {
+            MediumEvent::Scatter { distance } => (distance, &self.scatter_event),
+            MediumEvent::Absorb { distance } => (distance, &self.absorb_event),
+            MediumEvent::Pass => return false,
+        };
+        rec.t = t_enter + distance / ray_length;
+        rec.material = Arc::clone(event);
         rec.point = r.at(rec.t);
 
         // Arbitrary normal/face; medium scattering doesn't depend on surface orientation.
         rec.normal = Vec3::new(1.0, 0.0, 0.0);
         rec.front_face = true;
-        rec.material = Arc::clone(&self.phase_function);
 
         true
     }
 
+    /// Fraction of light passing through the medium along the ray (Beer-Lambert).
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        match boundary_span(&self.boundary, r, ray_t) {
+            Some((t_enter, t_exit)) => self.medium.transmittance((t_exit - t_enter) * r.direction.length()),
+            None => Color::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Per-channel weight of the ray passing through the medium along the ray without a collision.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        match boundary_span(&self.boundary, r, ray_t) {
+            Some((t_enter, t_exit)) => self.medium.tracking_weight((t_exit - t_enter) * r.direction.length()),
+            None => Color::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Whether the medium weights the channels of rays passing through it.
+    pub fn is_chromatic(&self) -> bool {
+        !self.medium.is_gray()
+    }
+
     /// Medium bounding box is the bounding box of its boundary.
     pub fn bounding_box(&self) -> &AABB {
         &self.bounding_box
     }
 }
 
+/// Ray parameters where the ray enters and exits a convex boundary, clamped to the ray's active
+/// interval. None if the ray misses the boundary or the clamped span is empty.
+pub(super) fn boundary_span(boundary: &Hittable, r: &Ray, ray_t: &Interval) -> Option<(f64, f64)> {
+    let mut rec1 = HitRecord::new();
+    let mut rec2 = HitRecord::new();
+
+    // Find entry/exit points on the convex boundary.
+    if !boundary.hit(r, &Interval::new(f64::NEG_INFINITY, f64::INFINITY), &mut rec1) {
+        return None;
+    }
+    if !boundary.hit(r, &Interval::new(rec1.t + EPSILON, f64::INFINITY), &mut rec2) {
+        return None;
+    }
+
+    // Clamp to the ray's active interval.
+    let t_enter = rec1.t.max(ray_t.min).max(0.0);
+    let t_exit = rec2.t.min(ray_t.max);
+    if t_enter >= t_exit { None } else { Some((t_enter, t_exit)) }
+}
+
 // From ConstantMedium to Hittable implementation
 impl From<ConstantMedium> for Hittable {
     fn from(medium: ConstantMedium) -> Self {
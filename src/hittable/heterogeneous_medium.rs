@@ -0,0 +1,152 @@
+use super::constant_medium::boundary_span;
+use super::{Cuboid, Hittable, HitRecord, AABB};
+
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::prelude::random_f64;
+use crate::ray::Ray;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::volume::{DensityField, VoxelGrid};
+
+use std::sync::Arc;
+
+/// Transmittance below which ratio tracking plays Russian roulette.
+const ROULETTE_THRESHOLD: f64 = 0.1;
+
+/// A medium whose density varies through space (a voxel grid or procedural noise), bounded by a
+/// convex hittable. Free paths are sampled against a majorant, the densest the medium can be, so
+/// the varying density never has to be integrated along the ray.
+#[derive(Clone)]
+pub struct HeterogeneousMedium {
+    boundary: Arc<Hittable>,
+    density: Arc<DensityField>,
+    density_scale: f64, // Extinction coefficient at unit field density
+    majorant: f64,      // Upper bound on the extinction coefficient inside the boundary
+    phase_function: Arc<Material>,
+    bounding_box: AABB,
+}
+
+impl HeterogeneousMedium {
+    /// Constructor from a density field, scaled to an extinction coefficient by `density_scale`,
+    /// and a solid color scattering albedo.
+    pub fn new(boundary: Arc<Hittable>, density: impl Into<DensityField>, density_scale: f64, albedo: &Color) -> Self {
+        let density: DensityField = density.into();
+        let bbox = *boundary.bounding_box();
+        let density_scale = density_scale.max(0.0);
+        Self {
+            boundary,
+            majorant: density_scale * density.max_density(),
+            density: Arc::new(density),
+            density_scale,
+            phase_function: Material::isotropic(*albedo),
+            bounding_box: bbox,
+        }
+    }
+
+    /// Constructor for a voxel grid filling its own bounds, e.g. a loaded volume file.
+    pub fn from_grid(grid: VoxelGrid, density_scale: f64, albedo: &Color) -> Self {
+        let bounds = grid.bounds();
+        let min = Vec3::new(bounds.x.min, bounds.y.min, bounds.z.min);
+        let max = Vec3::new(bounds.x.max, bounds.y.max, bounds.z.max);
+        let boundary = Cuboid::new(&min, &max, Material::isotropic(*albedo));
+        Self::new(Arc::new(boundary.into()), grid, density_scale, albedo)
+    }
+
+    /// Replace the isotropic phase function, e.g. with a forward-scattering Henyey-Greenstein one.
+    pub fn with_phase_function(mut self, phase_function: Arc<Material>) -> Self {
+        self.phase_function = phase_function;
+        self
+    }
+
+    /// Volume hit method. Sample a real scattering event with delta tracking: step through the
+    /// medium as if it had the majorant density everywhere, and accept each tentative collision
+    /// with probability of the actual density over the majorant.
+    #[inline]
+    pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
+        if self.majorant <= 0.0 {
+            return false;
+        }
+        let Some((t_enter, t_exit)) = boundary_span(&self.boundary, r, ray_t) else {
+            return false;
+        };
+
+        let step = 1.0 / (self.majorant * r.direction.length());
+        let mut t = t_enter;
+        loop {
+            t -= (1.0 - random_f64()).ln() * step;
+            if t >= t_exit {
+                return false;
+            }
+
+            let point = r.at(t);
+            if random_f64() * self.majorant < self.extinction(&point) {
+                rec.t = t;
+                rec.point = point;
+
+                // Arbitrary normal/face; medium scattering doesn't depend on surface orientation.
+                rec.normal = Vec3::new(1.0, 0.0, 0.0);
+                rec.front_face = true;
+                rec.material = Arc::clone(&self.phase_function);
+                return true;
+            }
+        }
+    }
+
+    /// Estimate the fraction of light passing through the medium along the ray with ratio tracking:
+    /// the same tentative collisions as delta tracking, each attenuating by the null-collision
+    /// probability instead of terminating. Unbiased, and much less noisy than a binary estimate.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        let transmittance = self.ratio_tracking(r, ray_t);
+        Color::new(transmittance, transmittance, transmittance)
+    }
+
+    /// Medium bounding box is the bounding box of its boundary.
+    pub fn bounding_box(&self) -> &AABB {
+        &self.bounding_box
+    }
+
+    /// Ratio tracking estimate of the scalar transmittance along the ray.
+    fn ratio_tracking(&self, r: &Ray, ray_t: &Interval) -> f64 {
+        if self.majorant <= 0.0 {
+            return 1.0;
+        }
+        let Some((t_enter, t_exit)) = boundary_span(&self.boundary, r, ray_t) else {
+            return 1.0;
+        };
+
+        let step = 1.0 / (self.majorant * r.direction.length());
+        let mut transmittance = 1.0;
+        let mut t = t_enter;
+        loop {
+            t -= (1.0 - random_f64()).ln() * step;
+            if t >= t_exit {
+                return transmittance;
+            }
+
+            transmittance *= 1.0 - self.extinction(&r.at(t)) / self.majorant;
+
+            // Russian roulette once little light gets through, to end long walks in thick media
+            if transmittance < ROULETTE_THRESHOLD {
+                let survive = transmittance.max(0.05);
+                if random_f64() >= survive {
+                    return 0.0;
+                }
+                transmittance /= survive;
+            }
+        }
+    }
+
+    /// Extinction coefficient at a point, clamped to the majorant.
+    #[inline]
+    fn extinction(&self, point: &Vec3) -> f64 {
+        (self.density_scale * self.density.density(point)).min(self.majorant)
+    }
+}
+
+// From HeterogeneousMedium to Hittable implementation
+impl From<HeterogeneousMedium> for Hittable {
+    fn from(medium: HeterogeneousMedium) -> Self {
+        Hittable::HeterogeneousMedium(medium)
+    }
+}
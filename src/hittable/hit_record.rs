@@ -14,6 +14,7 @@ pub struct HitRecord {
     pub u: f64,
     pub v: f64,
     pub front_face: bool,
+    pub outside_ior: f64, // Refractive index beyond the surface, set by the integrator for nested dielectrics
 }
 
 impl HitRecord {
@@ -27,6 +28,7 @@ impl HitRecord {
             u: 0.0,
             v: 0.0,
             front_face: false,
+            outside_ior: 1.0,
         }
     }
 
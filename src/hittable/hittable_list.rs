@@ -1,6 +1,7 @@
 use super::{HitRecord, Hittable, BVHNode, AABB};
 
 use crate::prelude::{random_usize};
+use crate::color::Color;
 use crate::ray::Ray;
 use crate::interval::Interval;
 use crate::vec3::{Point3, Vec3};
@@ -73,6 +74,28 @@ impl HittableList {
         hit_anything
     }
 
+    /// Product of the transmittance through every object in the list.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        let mut transmittance = Color::new(1.0, 1.0, 1.0);
+        for object in &self.objects {
+            transmittance = transmittance * object.transmittance(r, ray_t);
+            if transmittance.near_zero() {
+                return Color::zero();
+            }
+        }
+        transmittance
+    }
+
+    /// Product of the weights of passing through chromatic media in the list.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        self.objects.iter().fold(Color::new(1.0, 1.0, 1.0), |weight, object| weight * object.medium_weight(r, ray_t))
+    }
+
+    /// Whether any object in the list holds chromatic media.
+    pub fn has_chromatic_media(&self) -> bool {
+        self.objects.iter().any(|object| object.has_chromatic_media())
+    }
+
     /// Get bounding box of the hittable list.
     pub fn bounding_box(&self) -> &AABB {
         &self.bounding_box
@@ -0,0 +1,142 @@
+use super::{Hittable, HitRecord, AABB};
+
+use crate::interval::Interval;
+use crate::color::Color;
+use crate::ray::Ray;
+use crate::transform::{AnimatedTransform, Transform};
+use crate::vec3::{Point3, Vec3};
+
+use std::sync::Arc;
+
+/// An instance of a Hittable object placed by an affine transform: any rotation, non-uniform
+/// scale, shear or mirroring. The object is shared, so one mesh can be placed many times for the
+/// cost of a transform each. The transform may be animated, blurring the object over the shutter.
+#[derive(Clone)]
+pub struct Instance {
+    object: Arc<Hittable>,
+    placement: Placement,
+    bounding_box: AABB,
+}
+
+/// Where an instance is, fixed or changing with the ray time.
+#[derive(Clone)]
+enum Placement {
+    Static(Box<Transform>),
+    Animated(AnimatedTransform),
+}
+
+impl Instance {
+    /// Constructor from a shared object and the transform from its own space to the world.
+    pub fn new(object: impl Into<Arc<Hittable>>, transform: Transform) -> Self {
+        let object = object.into();
+        let bounding_box = transform.bounding_box(object.bounding_box());
+        Self {
+            object,
+            placement: Placement::Static(Box::new(transform)),
+            bounding_box,
+        }
+    }
+
+    /// Constructor for a moving instance, placed at each ray's time by keyframed transforms.
+    /// The bounding box covers the whole animation.
+    pub fn animated(object: impl Into<Arc<Hittable>>, motion: AnimatedTransform) -> Self {
+        let object = object.into();
+        let bounding_box = motion.bounding_box(object.bounding_box());
+        Self {
+            object,
+            placement: Placement::Animated(motion),
+            bounding_box,
+        }
+    }
+
+    /// Get the bounding box of the transformed object.
+    pub fn bounding_box(&self) -> &AABB {
+        &self.bounding_box
+    }
+
+    /// Check if a ray hits the transformed object.
+    #[inline]
+    pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
+        self.with_transform(r.time, |transform| {
+            // The object-space direction is not normalized, so t is the same in both spaces
+            if !self.object.hit(&transform.inverse_ray(r), ray_t, rec) {
+                return false;
+            }
+
+            // The normal already faces the object-space ray, and the inverse transpose keeps that
+            // orientation relative to the world ray, so front_face carries over.
+            rec.point = transform.point(&rec.point);
+            rec.normal = Vec3::unit_vector(&transform.normal(&rec.normal));
+
+            true
+        })
+    }
+
+    /// Transmittance along the ray through the transformed object.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        self.with_transform(r.time, |transform| self.object.transmittance(&transform.inverse_ray(r), ray_t))
+    }
+
+    /// Weight of the ray passing through chromatic media in the transformed object.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        if !self.object.has_chromatic_media() {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+        self.with_transform(r.time, |transform| self.object.medium_weight(&transform.inverse_ray(r), ray_t))
+    }
+
+    /// Whether the transformed object holds chromatic media.
+    pub fn has_chromatic_media(&self) -> bool {
+        self.object.has_chromatic_media()
+    }
+
+    /// Get the PDF value for a ray hitting the transformed object from a given origin in a given direction.
+    /// Non-rigid transforms stretch solid angles, so the object-space density is converted with the
+    /// Jacobian of the direction mapping, |det A| / |A w|^3 for the linear map A and unit direction w.
+    /// Moving instances are sampled where they are at the start of their motion.
+    pub fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+        self.with_transform(self.rest_time(), |transform| {
+            let direction_obj = transform.inverse_vector(&Vec3::unit_vector(direction));
+            let length = direction_obj.length();
+            if length <= 0.0 {
+                return 0.0;
+            }
+
+            let pdf = self.object.pdf_value(&transform.inverse_point(origin), &direction_obj);
+            pdf / (transform.determinant().abs() * length * length * length)
+        })
+    }
+
+    /// Generate a random direction from the given 'origin' towards the transformed object.
+    pub fn random(&self, origin: &Point3) -> Vec3 {
+        self.with_transform(self.rest_time(), |transform| {
+            let dir_obj = self.object.random(&transform.inverse_point(origin));
+            transform.vector(&dir_obj)
+        })
+    }
+
+    /// Run `f` with the transform in effect at the given time.
+    #[inline]
+    fn with_transform<R>(&self, time: f64, f: impl FnOnce(&Transform) -> R) -> R {
+        match &self.placement {
+            Placement::Static(transform) => f(transform),
+            Placement::Animated(motion) => f(&motion.at(time)),
+        }
+    }
+
+    /// Time at which light sampling sees the instance, consistently for `pdf_value` and `random`.
+    #[inline]
+    fn rest_time(&self) -> f64 {
+        match &self.placement {
+            Placement::Static(_) => 0.0,
+            Placement::Animated(motion) => motion.start_time(),
+        }
+    }
+}
+
+// From Instance to Hittable implementation
+impl From<Instance> for Hittable {
+    fn from(instance: Instance) -> Self {
+        Hittable::Instance(instance)
+    }
+}
@@ -5,7 +5,9 @@ pub mod hittable_list;
 pub mod aabb;
 pub mod translate;
 pub mod rotate_y;
+pub mod instance;
 pub mod constant_medium;
+pub mod heterogeneous_medium;
 pub mod sphere;
 pub mod quad;
 pub mod cuboid;
@@ -18,17 +20,23 @@ pub use hittable_list::HittableList;
 pub use aabb::AABB;
 pub use translate::Translate;
 pub use rotate_y::RotateY;
+pub use instance::Instance;
 pub use constant_medium::ConstantMedium;
+pub use heterogeneous_medium::HeterogeneousMedium;
 pub use sphere::Sphere;
 pub use quad::Quad;
 pub use cuboid::Cuboid;
 pub use triangle::Triangle;
 pub use triangle_mesh::TriangleMesh;
 
+use crate::color::Color;
+use crate::transform::{AnimatedTransform, Transform};
 use crate::ray::Ray;
 use crate::interval::Interval;
 use crate::vec3::{Point3, Vec3};
 
+use std::sync::Arc;
+
 // TODO: move Hittable enum to its own file
 /// Enum representing different types of Hittable objects.
 #[derive(Clone)]
@@ -40,7 +48,9 @@ pub enum Hittable {
     Cuboid(Cuboid),
     Translate(Translate),
     RotateY(RotateY),
+    Instance(Instance),
     ConstantMedium(ConstantMedium),
+    HeterogeneousMedium(HeterogeneousMedium),
     Triangle(Triangle),
     TriangleMesh(TriangleMesh),
     // Etc.
@@ -55,7 +65,9 @@ impl Hittable {
             Hittable::BVHNode(node) => node.hit(r, ray_t, rec),
             Hittable::Translate(translate) => translate.hit(r, ray_t, rec),
             Hittable::RotateY(rotate_y) => rotate_y.hit(r, ray_t, rec),
+            Hittable::Instance(instance) => instance.hit(r, ray_t, rec),
             Hittable::ConstantMedium(medium) => medium.hit(r, ray_t, rec),
+            Hittable::HeterogeneousMedium(medium) => medium.hit(r, ray_t, rec),
             Hittable::Sphere(sphere) => sphere.hit(r, ray_t, rec),
             Hittable::Quad(quad) => quad.hit(r, ray_t, rec),
             Hittable::Cuboid(cuboid) => cuboid.hit(r, ray_t, rec),
@@ -73,7 +85,9 @@ impl Hittable {
             Hittable::BVHNode(node) => node.bounding_box(),
             Hittable::Translate(translate) => translate.bounding_box(),
             Hittable::RotateY(rotate_y) => rotate_y.bounding_box(),
+            Hittable::Instance(instance) => instance.bounding_box(),
             Hittable::ConstantMedium(medium) => medium.bounding_box(),
+            Hittable::HeterogeneousMedium(medium) => medium.bounding_box(),
             Hittable::Sphere(sphere) => sphere.bounding_box(),
             Hittable::Quad(quad) => quad.bounding_box(),
             Hittable::Cuboid(cuboid) => cuboid.bounding_box(),
@@ -83,12 +97,64 @@ impl Hittable {
         }
     }
 
+    /// Fraction of light per channel that passes along the ray within `ray_t`, for shadow rays. Surfaces block
+    /// completely, while participating media let part of it through. Invisible surfaces, which light
+    /// passes straight through, are left to the integrator, which tracks the volumes they bound.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        match self {
+            Hittable::HittableList(list) => list.transmittance(r, ray_t),
+            Hittable::BVHNode(node) => node.transmittance(r, ray_t),
+            Hittable::Translate(translate) => translate.transmittance(r, ray_t),
+            Hittable::RotateY(rotate_y) => rotate_y.transmittance(r, ray_t),
+            Hittable::Instance(instance) => instance.transmittance(r, ray_t),
+            Hittable::ConstantMedium(medium) => medium.transmittance(r, ray_t),
+            Hittable::HeterogeneousMedium(medium) => medium.transmittance(r, ray_t),
+            _ => {
+                let mut rec = HitRecord::new();
+                if self.hit(r, ray_t, &mut rec) && rec.material.passthrough_tint().is_none() {
+                    Color::zero()
+                } else {
+                    Color::new(1.0, 1.0, 1.0)
+                }
+            }
+        }
+    }
+
+    /// Per-channel weight of the ray passing through chromatic constant media within `ray_t` without
+    /// colliding. Their hits only place collisions, and leave this weight to the integrator, since
+    /// it depends on the closest surface the ray reaches. White for everything else.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        match self {
+            Hittable::HittableList(list) => list.medium_weight(r, ray_t),
+            Hittable::BVHNode(node) => node.medium_weight(r, ray_t),
+            Hittable::Translate(translate) => translate.medium_weight(r, ray_t),
+            Hittable::RotateY(rotate_y) => rotate_y.medium_weight(r, ray_t),
+            Hittable::Instance(instance) => instance.medium_weight(r, ray_t),
+            Hittable::ConstantMedium(medium) => medium.medium_weight(r, ray_t),
+            _ => Color::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Whether the object holds chromatic constant media, so rays through it need `medium_weight`.
+    pub fn has_chromatic_media(&self) -> bool {
+        match self {
+            Hittable::HittableList(list) => list.has_chromatic_media(),
+            Hittable::BVHNode(node) => node.has_chromatic_media(),
+            Hittable::Translate(translate) => translate.has_chromatic_media(),
+            Hittable::RotateY(rotate_y) => rotate_y.has_chromatic_media(),
+            Hittable::Instance(instance) => instance.has_chromatic_media(),
+            Hittable::ConstantMedium(medium) => medium.is_chromatic(),
+            _ => false,
+        }
+    }
+
     /// Get the PDF value for a ray hitting the Hittable object from a given origin in a given direction.
     pub fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
         match self {
             Hittable::HittableList(list) => list.pdf_value(origin, direction),
             Hittable::Translate(translate) => translate.pdf_value(origin, direction),
             Hittable::RotateY(rotate_y) => rotate_y.pdf_value(origin, direction),
+            Hittable::Instance(instance) => instance.pdf_value(origin, direction),
             Hittable::Sphere(sphere) => sphere.pdf_value(origin, direction),
             Hittable::Quad(quad) => quad.pdf_value(origin, direction),
             Hittable::Cuboid(cuboid) => cuboid.pdf_value(origin, direction),
@@ -104,6 +170,7 @@ impl Hittable {
             Hittable::HittableList(list) => list.random(origin),
             Hittable::Translate(translate) => translate.random(origin),
             Hittable::RotateY(rotate_y) => rotate_y.random(origin),
+            Hittable::Instance(instance) => instance.random(origin),
             Hittable::Sphere(sphere) => sphere.random(origin),
             Hittable::Quad(quad) => quad.random(origin),
             Hittable::Cuboid(cuboid) => cuboid.random(origin),
@@ -124,6 +191,16 @@ impl Hittable {
         Hittable::RotateY(RotateY::new(object, angle_deg))
     }
 
+    /// Place a shared object with an affine transform, e.g. one of many copies of a mesh.
+    pub fn instance(object: impl Into<Arc<Hittable>>, transform: Transform) -> Hittable {
+        Hittable::Instance(Instance::new(object, transform))
+    }
+
+    /// Move a shared object over time with keyframed transforms, for motion blur.
+    pub fn animated(object: impl Into<Arc<Hittable>>, motion: AnimatedTransform) -> Hittable {
+        Hittable::Instance(Instance::animated(object, motion))
+    }
+
     /// Rotate about the Y axis, then translate by an offset.
     pub fn rotate_y_translate(object: impl Into<Hittable>, angle_deg: f64, offset: Vec3) -> Hittable {
         Hittable::translate(Hittable::rotate_y(object, angle_deg), offset)
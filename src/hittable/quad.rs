@@ -1,13 +1,18 @@
 use super::{Hittable, HitRecord, AABB};
 
+use crate::distribution::Distribution2D;
 use crate::interval::Interval;
-use crate::material::Material;
+use crate::material::{DiffuseLight, Material};
+use crate::photometry::LightPower;
 use crate::ray::Ray;
 use crate::vec3::{Point3, Vec3};
 use crate::prelude::{EPSILON, random_f64};
 
 use std::sync::Arc;
 
+/// Resolution of the grid used to light-sample textured emitters by brightness.
+const EMISSION_SAMPLING_RESOLUTION: usize = 128;
+
 /// A quadrilateral defined by a point and two edge vectors.
 #[derive(Clone)]
 pub struct Quad {
@@ -20,6 +25,7 @@ pub struct Quad {
     D: f64, // Normal dot Q
     w: Vec3, // n / (n dot n)
     area: f64,
+    emission_distribution: Option<Arc<Distribution2D>>, // Luminance of a textured emitter over (u, v), None for uniform sampling
 }
 
 impl Quad {
@@ -42,7 +48,9 @@ impl Quad {
             D,
             w: n / Vec3::dot(&n, &n),
             area: n.length(),
+            emission_distribution: None,
         }
+        .with_emission_sampling()
     }
 
     /// Get the bounding box of the quad.
@@ -106,13 +114,21 @@ impl Quad {
 
         let distance_squared = rec.t * rec.t * direction.length_squared();
         let cosine = (Vec3::dot(direction, &rec.normal) / direction.length()).abs();
+        if let Some(distribution) = &self.emission_distribution {
+            return distribution.pdf(rec.u, rec.v) * distance_squared / (cosine * self.area);
+        }
 
         distance_squared / (cosine * self.area)
     }
 
     /// Generate a random direction from the given origin towards the quad.
+    /// Textured emitters are sampled proportional to their brightness.
     pub fn random(&self, origin: &Point3) -> Vec3 {
-        let p = self.Q + (random_f64() * self.edge_u) + (random_f64() * self.edge_v);
+        let (u, v) = match &self.emission_distribution {
+            Some(distribution) => distribution.sample(random_f64(), random_f64()).0,
+            None => (random_f64(), random_f64()),
+        };
+        let p = self.Q + (u * self.edge_u) + (v * self.edge_v);
         p - *origin
     }
 
@@ -120,6 +136,30 @@ impl Quad {
     pub fn area(&self) -> f64 {
         self.area
     }
+
+    /// Replace the material with an emitter whose brightness is given in physical units over this quad's area.
+    pub fn with_emitter(mut self, light: DiffuseLight, power: LightPower) -> Self {
+        self.material = light.with_power(power, self.area).into();
+        self.with_emission_sampling()
+    }
+
+    /// Tabulate the emitted luminance over the quad when its material has a textured emission.
+    fn with_emission_sampling(mut self) -> Self {
+        self.emission_distribution = self.material.emission_texture().map(|tex| {
+            let n = EMISSION_SAMPLING_RESOLUTION;
+            let mut func = Vec::with_capacity(n * n);
+            for j in 0..n {
+                for i in 0..n {
+                    let u = (i as f64 + 0.5) / n as f64;
+                    let v = (j as f64 + 0.5) / n as f64;
+                    let p = self.Q + (u * self.edge_u) + (v * self.edge_v);
+                    func.push(tex.value(u, v, &p).luminance());
+                }
+            }
+            Arc::new(Distribution2D::new(&func, n, n))
+        });
+        self
+    }
 }
 
 // From Quad to Hittable implementation
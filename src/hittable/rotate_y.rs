@@ -1,6 +1,7 @@
 use super::{Hittable, HitRecord, AABB};
 
 use crate::interval::Interval;
+use crate::color::Color;
 use crate::ray::Ray;
 use crate::vec3::{Point3, Vec3};
 use crate::prelude::degrees_to_radians;
@@ -75,21 +76,7 @@ impl RotateY {
     /// Check if a ray hits the rotated object.
     #[inline]
     pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
-        // Transform the ray origin from world space to object space (rotate by -theta)
-        let origin = Point3::new(
-            self.cos_theta * r.origin.x() - self.sin_theta * r.origin.z(),
-            r.origin.y(),
-            self.sin_theta * r.origin.x() + self.cos_theta * r.origin.z(),
-        );
-
-        // Transform the direction from world space to object space (rotate by -theta)
-        let direction = Vec3::new(
-            self.cos_theta * r.direction.x() - self.sin_theta * r.direction.z(),
-            r.direction.y(),
-            self.sin_theta * r.direction.x() + self.cos_theta * r.direction.z(),
-        );
-
-        let rotated_r = Ray::new_with_time(origin, direction, r.time);
+        let rotated_r = self.to_object_space(r);
 
         // Determine whether an intersection exists in object space
         if !self.object.hit(&rotated_r, ray_t, rec) {
@@ -112,6 +99,21 @@ impl RotateY {
         true
     }
 
+    /// Transmittance along the ray through the rotated object.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        self.object.transmittance(&self.to_object_space(r), ray_t)
+    }
+
+    /// Weight of the ray passing through chromatic media in the rotated object.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        self.object.medium_weight(&self.to_object_space(r), ray_t)
+    }
+
+    /// Whether the rotated object holds chromatic media.
+    pub fn has_chromatic_media(&self) -> bool {
+        self.object.has_chromatic_media()
+    }
+
     /// Get the PDF value for a ray hitting the rotated object from a given origin in a given direction.
     pub fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
         // Rotate origin and direction into object space (by -theta)
@@ -148,6 +150,26 @@ impl RotateY {
             -self.sin_theta * dir_obj.x() + self.cos_theta * dir_obj.z(),
         )
     }
+
+    /// Transform a ray from world space to object space (rotate by -theta).
+    #[inline]
+    fn to_object_space(&self, r: &Ray) -> Ray {
+        // Transform the ray origin from world space to object space (rotate by -theta)
+        let origin = Point3::new(
+            self.cos_theta * r.origin.x() - self.sin_theta * r.origin.z(),
+            r.origin.y(),
+            self.sin_theta * r.origin.x() + self.cos_theta * r.origin.z(),
+        );
+
+        // Transform the direction from world space to object space (rotate by -theta)
+        let direction = Vec3::new(
+            self.cos_theta * r.direction.x() - self.sin_theta * r.direction.z(),
+            r.direction.y(),
+            self.sin_theta * r.direction.x() + self.cos_theta * r.direction.z(),
+        );
+
+        Ray::new_with_time(origin, direction, r.time)
+    }
 }
 
 // From RotateY to Hittable implementation
@@ -33,7 +33,8 @@ impl Sphere {
         }
     }
 
-    /// Constructor for a Moving Sphere between two centers.
+    /// Constructor for a Moving Sphere between two centers, travelling over times [0,1] and
+    /// holding at its end positions outside them.
     pub fn new_moving( center1: &Point3, center2: &Point3, radius: f64, material: Arc<Material>) -> Self {
         Self {
             center: Ray::new(*center1, *center2 - *center1),
@@ -51,7 +52,8 @@ impl Sphere {
     /// Check for ray-sphere intersection.
     #[inline]
     pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
-        let current_center: Point3 = self.center.at(r.time);
+        // Clamp the time so the center stays within the bounding box of the motion
+        let current_center: Point3 = self.center.at(r.time.clamp(0.0, 1.0));
         // Calculate the discriminant of the quadratic equation for ray-sphere intersection
         let oc: Vec3 = current_center - r.origin;
         let a = r.direction.length_squared();
@@ -1,6 +1,7 @@
 use super::{Hittable, HitRecord, AABB};
 
 use crate::interval::Interval;
+use crate::color::Color;
 use crate::ray::Ray;
 use crate::vec3::{Point3, Vec3};
 
@@ -49,6 +50,23 @@ impl Translate {
         true
     }
 
+    /// Transmittance along the ray through the translated object.
+    pub fn transmittance(&self, r: &Ray, ray_t: &Interval) -> Color {
+        let offset_r = Ray::new_with_time(r.origin - self.offset, r.direction, r.time);
+        self.object.transmittance(&offset_r, ray_t)
+    }
+
+    /// Weight of the ray passing through chromatic media in the translated object.
+    pub fn medium_weight(&self, r: &Ray, ray_t: &Interval) -> Color {
+        let offset_r = Ray::new_with_time(r.origin - self.offset, r.direction, r.time);
+        self.object.medium_weight(&offset_r, ray_t)
+    }
+
+    /// Whether the translated object holds chromatic media.
+    pub fn has_chromatic_media(&self) -> bool {
+        self.object.has_chromatic_media()
+    }
+
     /// Get the PDF value for a ray hitting the translated object from a given origin in a given direction.
     pub fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
         let origin_local = *origin - self.offset;
@@ -1,6 +1,7 @@
 use super::{AABB, HitRecord, Hittable};
 
-use crate::material::Material;
+use crate::material::{DiffuseLight, Material};
+use crate::photometry::LightPower;
 use crate::prelude::{EPSILON, Interval, random_f64};
 use crate::ray::Ray;
 use crate::vec3::{Point3, Vec3};
@@ -88,7 +89,19 @@ struct MeshGeometry {
     positions: Option<Vec<Point3>>,         // Original vertex positions
     face_indices: Option<Vec<[u32; 3]>>,    // Original face indices
     vertex_normals: Option<Vec<Vec3>>,      // Per-vertex normals for smooth shading
-    vertex_uvs: Option<Vec<(f64, f64)>>,    // Per-vertex UV coordinates for texturing
+    face_uvs: Option<Vec<[(f64, f64); 3]>>, // Per-corner UV coordinates in BVH leaf order, kept per face so UV seams survive quantization
+
+    // Per-vertex motion for deformation blur, None for a static mesh
+    motion: Option<MeshMotion>,
+}
+
+/// Per-vertex motion: the mesh at each keyframe time, interpolated linearly by `Ray::time` and held
+/// constant outside the keyed range. Vertices moving linearly keep every triangle inside the union
+/// of its keyframe bounds, so the BVH is built over those swept bounds.
+struct MeshMotion {
+    times: Vec<f64>,                         // Keyframe times, ascending
+    triangles: Vec<Vec<SimpleTriangle>>,     // Triangles per keyframe, in BVH leaf order
+    vertex_normals: Option<Vec<Vec<Vec3>>>,  // Per-vertex normals per keyframe for smooth shading
 }
 
 /// Public mesh hittable with shared geometry and a single material.
@@ -96,6 +109,7 @@ struct MeshGeometry {
 pub struct TriangleMesh {
     geometry: Arc<MeshGeometry>,
     material: Arc<Material>,
+    emission_cdf: Option<Arc<Vec<f64>>>, // Face CDF weighted by area and emitted luminance, None for area-only sampling
 }
 
 impl TriangleMesh {
@@ -113,9 +127,63 @@ impl TriangleMesh {
         material: Arc<Material>,
         smoothed_normals: bool,
     ) -> Self {
+        Self::build(vec![(0.0, positions)], face_indices, None, material, smoothed_normals)
+    }
+
+    /// Build a deforming `TriangleMesh` for motion blur from vertex positions at keyframe times
+    /// (in any order), sharing one triangle index buffer. Each ray sees the mesh interpolated to its
+    /// time. Vertices are not deduplicated, since merged vertices could move apart between keyframes.
+    pub fn new_deforming(
+        keyframes: Vec<(f64, Vec<Point3>)>,
+        face_indices: Vec<[u32; 3]>,
+        material: Arc<Material>,
+        smoothed_normals: bool,
+    ) -> Self {
+        assert!(!keyframes.is_empty(), "Deforming mesh needs at least one keyframe");
+        assert!(keyframes.iter().all(|(_, positions)| positions.len() == keyframes[0].1.len()),
+            "Every keyframe must have the same number of vertex positions");
+        Self::build(keyframes, face_indices, None, material, smoothed_normals)
+    }
+
+    /// Build a `TriangleMesh` like `new`, with per-vertex UV coordinates (one per position) for
+    /// textured materials and emitters. Without UVs, hits report the triangle's barycentric coordinates.
+    pub fn new_with_uvs(
+        positions: Vec<Point3>, 
+        face_indices: Vec<[u32; 3]>, 
+        vertex_uvs: Vec<(f64, f64)>,
+        material: Arc<Material>,
+        smoothed_normals: bool,
+    ) -> Self {
+        assert_eq!(vertex_uvs.len(), positions.len(), "Mesh must have one UV per vertex position");
+        Self::build(vec![(0.0, positions)], face_indices, Some(vertex_uvs), material, smoothed_normals)
+    }
+
+    /// Build from vertex positions at one or more keyframe times. A single keyframe makes a static mesh.
+    fn build(
+        mut keyframes: Vec<(f64, Vec<Point3>)>,
+        face_indices: Vec<[u32; 3]>, 
+        vertex_uvs: Option<Vec<(f64, f64)>>,
+        material: Arc<Material>,
+        smoothed_normals: bool,
+    ) -> Self {
+        // Gather per-corner UVs with the original indices, before quantization merges vertices
+        let face_uvs: Option<Vec<[(f64, f64); 3]>> = vertex_uvs.map(|uvs| face_indices.iter()
+            .map(|&[i0, i1, i2]| [uvs[i0 as usize], uvs[i1 as usize], uvs[i2 as usize]])
+            .collect());
+
         // ----- 1. Quantize vertices to deduplicate and clean up the mesh. -----
-        let (positions, face_indices) = quantize_vertices(positions, face_indices);
-        println!("Vertices after quantization: {}", positions.len());
+        // Deforming meshes keep their vertices, which must stay in step across keyframes.
+        keyframes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (key_times, mut frames): (Vec<f64>, Vec<Vec<Point3>>) = keyframes.into_iter().unzip();
+        let face_indices = if frames.len() == 1 {
+            let (positions, face_indices) = quantize_vertices(frames.pop().unwrap(), face_indices);
+            println!("Vertices after quantization: {}", positions.len());
+            frames.push(positions);
+            face_indices
+        } else {
+            face_indices
+        };
+        let positions: &[Point3] = &frames[0]; // Rest pose, used for light sampling
 
         let n_faces = face_indices.len();
         let n_pos = positions.len();
@@ -127,6 +195,7 @@ impl TriangleMesh {
         let mut face_centroids: Vec<Vec3> = Vec::with_capacity(n_faces);
         let mut face_normals: Vec<Vec3> = if smoothed_normals { Vec::with_capacity(n_faces) } else { Vec::new() };
         let mut valid_face_indices: Vec<[u32; 3]> = Vec::with_capacity(n_faces);
+        let mut valid_face_uvs: Vec<[(f64, f64); 3]> = Vec::with_capacity(if face_uvs.is_some() { n_faces } else { 0 });
 
         for (fi, &[i0, i1, i2]) in face_indices.iter().enumerate() {
             assert!((i0 as usize) < n_pos && (i1 as usize) < n_pos && (i2 as usize) < n_pos, 
@@ -142,8 +211,11 @@ impl TriangleMesh {
             }
 
             valid_face_indices.push([i0, i1, i2]);
+            if let Some(face_uvs) = &face_uvs { valid_face_uvs.push(face_uvs[fi]); }
 
-            let tri_bbox: AABB = AABB::from_point_triplet(&p0, &p1, &p2);
+            // Bounds swept over every keyframe
+            let tri_bbox: AABB = frames.iter().fold(AABB::empty(), |bbox, frame| AABB::merge(&bbox,
+                &AABB::from_point_triplet(&frame[i0 as usize], &frame[i1 as usize], &frame[i2 as usize])));
             face_bboxes.push(tri_bbox);
             face_centroids.push((p0 + p1 + p2) / 3.0);
 
@@ -176,6 +248,7 @@ impl TriangleMesh {
         let mut triangles: Vec<SimpleTriangle> = Vec::with_capacity(n_faces);
         let mut face_indices_reordered: Vec<[u32; 3]> = Vec::with_capacity(n_faces);
         let mut face_areas: Vec<f64> = Vec::with_capacity(n_faces);
+        let mut face_uvs_reordered: Vec<[(f64, f64); 3]> = Vec::with_capacity(valid_face_uvs.len());
 
         for &orig_face_index in &new_triangle_order {
             let [i0, i1, i2] = valid_face_indices[orig_face_index as usize];
@@ -188,6 +261,7 @@ impl TriangleMesh {
 
             triangles.push(SimpleTriangle { p0, e1, e2 });
             face_indices_reordered.push([i0, i1, i2]);
+            if face_uvs.is_some() { face_uvs_reordered.push(valid_face_uvs[orig_face_index as usize]); }
 
             let area: f64 = 0.5 * Vec3::cross(&e1, &e2).length();
             face_areas.push(area);
@@ -212,6 +286,30 @@ impl TriangleMesh {
             None
         };
 
+        // ----- 7. Triangles and normals at every keyframe for deforming meshes -----
+        let motion = (frames.len() > 1).then(|| MeshMotion {
+            triangles: frames.iter()
+                .map(|frame| face_indices_reordered.iter()
+                    .map(|&[i0, i1, i2]| {
+                        let p0 = frame[i0 as usize];
+                        SimpleTriangle { p0, e1: frame[i1 as usize] - p0, e2: frame[i2 as usize] - p0 }
+                    })
+                    .collect())
+                .collect(),
+            vertex_normals: smoothed_normals.then(|| frames.iter()
+                .map(|frame| {
+                    let normals: Vec<Vec3> = valid_face_indices.iter()
+                        .map(|&[i0, i1, i2]| {
+                            let p0 = frame[i0 as usize];
+                            Vec3::cross(&(frame[i1 as usize] - p0), &(frame[i2 as usize] - p0))
+                        })
+                        .collect();
+                    compute_vertex_normals(n_pos, &valid_face_indices, &normals)
+                })
+                .collect()),
+            times: key_times,
+        });
+
         // For default flat shading we don't need the original positions or per-vertex attributes,
         // everything intersection-related is already baked into `triangles`.
         // These slots are kept as `Option` so a future `with_smooth_shading` constructor
@@ -225,13 +323,16 @@ impl TriangleMesh {
             positions: None,
             face_indices: if smoothed_normals { Some(face_indices_reordered) } else { None },
             vertex_normals,
-            vertex_uvs: None,
+            face_uvs: face_uvs.map(|_| face_uvs_reordered),
+            motion,
         };
 
         Self {
             geometry: Arc::new(geometry),
             material,
+            emission_cdf: None,
         }
+        .with_emission_sampling()
     }
 
     /// Get the bounding box of the entire mesh.
@@ -240,6 +341,55 @@ impl TriangleMesh {
     pub fn triangle_count(&self) -> usize { self.geometry.triangles.len() }
     /// Get the total surface area of the mesh.
     pub fn total_area(&self) -> f64 { self.geometry.total_area }
+
+    /// Replace the material with an emitter whose brightness is given in physical units over the mesh's total area.
+    pub fn with_emitter(mut self, light: DiffuseLight, power: LightPower) -> Self {
+        self.material = light.with_power(power, self.geometry.total_area).into();
+        self.with_emission_sampling()
+    }
+
+    /// Weight face selection by emitted luminance when the material has a textured emission,
+    /// estimated per face from the texture at its corners and centroid.
+    fn with_emission_sampling(mut self) -> Self {
+        let Some(tex) = self.material.emission_texture() else {
+            self.emission_cdf = None;
+            return self;
+        };
+        let geometry: &MeshGeometry = &self.geometry;
+
+        let mut weights: Vec<f64> = Vec::with_capacity(geometry.triangles.len());
+        for (i, tri) in geometry.triangles.iter().enumerate() {
+            let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0 / 3.0, 1.0 / 3.0)];
+            let luminance: f64 = corners.iter()
+                .map(|&(b1, b2)| {
+                    let (u, v) = geometry.surface_uv(i, b1, b2);
+                    tex.value(u, v, &(tri.p0 + b1 * tri.e1 + b2 * tri.e2)).luminance().max(0.0)
+                })
+                .sum::<f64>() / corners.len() as f64;
+            weights.push(geometry.face_areas[i] * luminance);
+        }
+
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            self.emission_cdf = None;
+            return self;
+        }
+        let mut acc: f64 = 0.0;
+        let mut cdf: Vec<f64> = weights.iter().map(|w| { acc += w / total; acc }).collect();
+        if let Some(last) = cdf.last_mut() { *last = 1.0; }
+
+        self.emission_cdf = Some(Arc::new(cdf));
+        self
+    }
+
+    /// The face CDF used for light sampling.
+    #[inline]
+    fn sampling_cdf(&self) -> &[f64] {
+        match &self.emission_cdf {
+            Some(cdf) => cdf,
+            None => &self.geometry.face_area_cdf,
+        }
+    }
     
     /// Möller-Trumbore intersection of a single `SimpleTriangle`.
     /// Returns `Some((t, u, v))` on hit, where `(u, v)` are barycentric coordinates. Returns `None` on miss.
@@ -264,7 +414,9 @@ impl TriangleMesh {
         Some((t, u, v))
     }
 
-    pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
+    /// Traverse the mesh BVH for the closest hit. Returns `Some((t, triangle_index, u, v))`, where
+    /// `(u, v)` are the barycentric coordinates on the triangle at `triangle_index`.
+    fn closest_hit(&self, r: &Ray, ray_t: &Interval) -> Option<(f64, usize, f64, f64)> {
         let geometry: &MeshGeometry = &*self.geometry;
         
         // Iterative traversal stack. MAX_BVH_DEPTH is a safe upper bound.
@@ -295,7 +447,11 @@ impl TriangleMesh {
                 let end: usize = start + node.triangle_count as usize;
                 for i in start..end {
                     t_interval.max = closest_t; // Tighten bound
-                    let triangle = &geometry.triangles[i];
+                    let moved: SimpleTriangle;
+                    let triangle = match &geometry.motion {
+                        Some(motion) => { moved = motion.triangle(i, r.time); &moved }
+                        None => &geometry.triangles[i],
+                    };
                     if let Some((t, u, v)) = Self::hit_triangle(triangle, r, &t_interval) {
                         closest_t = t;
                         hit_anything = true;
@@ -331,64 +487,91 @@ impl TriangleMesh {
             }
         }
 
-        if hit_anything {
-            // Compute the face normal lazily: only for the closest hit triangle, and after confirming a hit
-            // instead of caching a Vec3 on every triangle.
-            let tri: &SimpleTriangle = &geometry.triangles[best_tri_index];
-
-            // Compute the face normal using vertex normals if available,
-            // otherwise fallback to flat normal from cross product of edges.
-            let geometric_normal = Vec3::unit_vector(&Vec3::cross(&tri.e1, &tri.e2));
-            let normal = if let (Some(vn), Some(fi)) = (&geometry.vertex_normals, &geometry.face_indices) {
-                let [i0, i1, i2] = fi[best_tri_index];
-                let n0 = vn[i0 as usize];
-                let n1 = vn[i1 as usize];
-                let n2 = vn[i2 as usize];
-                // Barycentric interpolation: (1 - u - v) * n0 + u * n1 + v * n2
-                let w = 1.0 - best_u - best_v;
-                let interpolated = w * n0 + best_u * n1 + best_v * n2;
-                Vec3::unit_vector(&interpolated)
-            } else { // Fallback to flat normal
-                geometric_normal
+        if hit_anything { Some((closest_t, best_tri_index, best_u, best_v)) } else { None }
+    }
+
+    pub fn hit(&self, r: &Ray, ray_t: &Interval, rec: &mut HitRecord) -> bool {
+        let geometry: &MeshGeometry = &self.geometry;
+        let Some((closest_t, best_tri_index, best_u, best_v)) = self.closest_hit(r, ray_t) else {
+            return false;
+        };
+
+        // Compute the face normal lazily: only for the closest hit triangle, and after confirming a hit
+        // instead of caching a Vec3 on every triangle.
+        let tri: SimpleTriangle = match &geometry.motion {
+            Some(motion) => motion.triangle(best_tri_index, r.time),
+            None => geometry.triangles[best_tri_index],
+        };
+
+        // Compute the face normal using vertex normals if available,
+        // otherwise fallback to flat normal from cross product of edges.
+        let geometric_normal = Vec3::unit_vector(&Vec3::cross(&tri.e1, &tri.e2));
+        let normal = if let (Some(vn), Some(fi)) = (&geometry.vertex_normals, &geometry.face_indices) {
+            let [i0, i1, i2] = fi[best_tri_index];
+            let vertex_normal = |i: u32| match &geometry.motion {
+                Some(motion) => motion.vertex_normal(i as usize, r.time).unwrap_or(vn[i as usize]),
+                None => vn[i as usize],
             };
+            let n0 = vertex_normal(i0);
+            let n1 = vertex_normal(i1);
+            let n2 = vertex_normal(i2);
+            // Barycentric interpolation: (1 - u - v) * n0 + u * n1 + v * n2
+            let w = 1.0 - best_u - best_v;
+            let interpolated = w * n0 + best_u * n1 + best_v * n2;
+            Vec3::unit_vector(&interpolated)
+        } else { // Fallback to flat normal
+            geometric_normal
+        };
 
-            // Update hit record with hit information
-            rec.t = closest_t;
-            rec.point = r.at(closest_t);
-            rec.material = Arc::clone(&self.material);
-            rec.set_face_normal(r, &normal);
-            let _ = (best_u, best_v); // Silence unused variable warning for now
-        }
+        // Update hit record with hit information
+        rec.t = closest_t;
+        rec.point = r.at(closest_t);
+        rec.material = Arc::clone(&self.material);
+        rec.set_face_normal(r, &normal);
+        (rec.u, rec.v) = geometry.surface_uv(best_tri_index, best_u, best_v);
 
-        hit_anything
+        true
     }
 
-    /// PDF value for a uniform-area sampler on the mesh surface: density is `1 / total_area`
-    /// over the surface, so the solid-angle PDF is `dist^2 / (cos_theta * total_area)`.
+    /// PDF value for sampling a point on the mesh surface: density is `1 / total_area` for uniform-area
+    /// sampling, so the solid-angle PDF is `dist^2 / (cos_theta * total_area)`. Textured emitters
+    /// instead use the face's selection probability over its area.
     pub fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
         let geometry: &MeshGeometry = &*self.geometry;
         
-        let ray = Ray::new(*origin, *direction);
-        let mut rec = HitRecord::new();
-        if !self.hit(&ray, &Interval::new(0.001, f64::INFINITY), &mut rec) {
+        // Deforming meshes are sampled in their rest pose, the first keyframe, as in `random`
+        let rest_time = geometry.motion.as_ref().map_or(0.0, |motion| motion.times[0]);
+        let ray = Ray::new_with_time(*origin, *direction, rest_time);
+        let Some((t, tri_index, _, _)) = self.closest_hit(&ray, &Interval::new(0.001, f64::INFINITY)) else {
             return 0.0;
-        }
-        
-        let distance_squared: f64 = rec.t * rec.t * direction.length_squared();
-        let cosine: f64 = (Vec3::dot(direction, &rec.normal) / direction.length()).abs();
-        distance_squared / (cosine * geometry.total_area)
+        };
+        let tri: &SimpleTriangle = &geometry.triangles[tri_index];
+        let normal = Vec3::unit_vector(&Vec3::cross(&tri.e1, &tri.e2));
+
+        // Density over the surface: probability of picking this face over its area
+        let area_density: f64 = match &self.emission_cdf {
+            Some(cdf) => {
+                let face_probability = cdf[tri_index] - if tri_index > 0 { cdf[tri_index - 1] } else { 0.0 };
+                face_probability / geometry.face_areas[tri_index]
+            }
+            None => 1.0 / geometry.total_area,
+        };
+
+        let distance_squared: f64 = t * t * direction.length_squared();
+        let cosine: f64 = (Vec3::dot(direction, &normal) / direction.length()).abs();
+        area_density * distance_squared / cosine
     }
 
     /// Sample a point on the mesh surface uniformly by area, returning the
     /// vector from `origin` to that point. Picks a face proportional to its area
-    /// via binary search on the CDF, then a uniform point inside it 
-    /// via the reflection trick.
+    /// (times its brightness for textured emitters) via binary search on the CDF,
+    /// then a uniform point inside it via the reflection trick.
     pub fn random(&self, origin: &Point3) -> Vec3 {
         let geometry: &MeshGeometry = &*self.geometry;
 
-        // Pick a face proportional to its area
+        // Pick a face proportional to its sampling weight
         let r: f64 = random_f64();
-        let face_index: usize = geometry.face_area_cdf
+        let face_index: usize = self.sampling_cdf()
             .partition_point(|&cum| cum < r);
         debug_assert!(face_index < geometry.triangles.len(), "Random sampling went out of bounds");
         let tri: &SimpleTriangle = &geometry.triangles[face_index];
@@ -411,6 +594,59 @@ impl From<TriangleMesh> for Hittable {
     }
 }
 
+impl MeshMotion {
+    /// The keyframes around a time and the blend factor between them.
+    #[inline]
+    fn segment(&self, time: f64) -> (usize, usize, f64) {
+        let last = self.times.len() - 1;
+        if time <= self.times[0] { return (0, 0, 0.0); }
+        if time >= self.times[last] { return (last, last, 0.0); }
+
+        let next = self.times.partition_point(|&key| key <= time);
+        let span = self.times[next] - self.times[next - 1];
+        let blend = if span > 0.0 { (time - self.times[next - 1]) / span } else { 1.0 };
+        (next - 1, next, blend)
+    }
+
+    /// The triangle at `tri_index` interpolated to a time. Edges interpolate like the vertices.
+    #[inline]
+    fn triangle(&self, tri_index: usize, time: f64) -> SimpleTriangle {
+        let (a, b, blend) = self.segment(time);
+        let ta = &self.triangles[a][tri_index];
+        let tb = &self.triangles[b][tri_index];
+        SimpleTriangle {
+            p0: ta.p0 + blend * (tb.p0 - ta.p0),
+            e1: ta.e1 + blend * (tb.e1 - ta.e1),
+            e2: ta.e2 + blend * (tb.e2 - ta.e2),
+        }
+    }
+
+    /// The smooth-shading normal of a vertex interpolated to a time, if the mesh has them.
+    #[inline]
+    fn vertex_normal(&self, vertex: usize, time: f64) -> Option<Vec3> {
+        let normals = self.vertex_normals.as_ref()?;
+        let (a, b, blend) = self.segment(time);
+        let (na, nb) = (normals[a][vertex], normals[b][vertex]);
+        Some(Vec3::unit_vector(&(na + blend * (nb - na))))
+    }
+}
+
+impl MeshGeometry {
+    /// Surface UV at barycentric coordinates `(b1, b2)` on the triangle at `tri_index`,
+    /// interpolated from the per-corner UVs, or the barycentric coordinates themselves without UVs.
+    #[inline]
+    fn surface_uv(&self, tri_index: usize, b1: f64, b2: f64) -> (f64, f64) {
+        match &self.face_uvs {
+            Some(face_uvs) => {
+                let [uv0, uv1, uv2] = face_uvs[tri_index];
+                let b0 = 1.0 - b1 - b2;
+                (b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0, b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1)
+            }
+            None => (b1, b2),
+        }
+    }
+}
+
 // =====================================================================
 // Geometry processing helpers
 // =====================================================================
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::color::Color;
 
@@ -16,7 +16,8 @@ impl ImageData {
     /// Searches for the  file in the current directory, in 'textures/' and '../textures/'.
     /// Writes to "data" in row-major order, 3 floats per pixel (R, G, B).
     /// 
-    /// Assumptions: The image is in a format supported by the 'image' crate and is RGB8.
+    /// Assumptions: The image is in a format supported by the 'image' crate. HDR formats
+    /// (.hdr, .exr) are already linear and kept unclamped; everything else is decoded from sRGB.
     pub fn new(filename: &str) -> Self {
         let search_paths = [ // Search in multiple locations
             PathBuf::from(filename),
@@ -33,8 +34,10 @@ impl ImageData {
 
                 // Convert sRGB -> linear (kept as f32)
                 let mut data = rgb_img.into_raw();
-                for c in data.iter_mut() {
-                    *c = srgb_unit_to_linear(*c);
+                if !is_hdr_path(path) {
+                    for c in data.iter_mut() {
+                        *c = srgb_unit_to_linear(*c);
+                    }
                 }
 
                 return Self { width: w, height: h, data };
@@ -50,6 +53,12 @@ impl ImageData {
         }
     }
 
+    /// Creates an image from linear RGB floats, row-major with 3 floats per pixel.
+    pub fn from_linear(width: u32, height: u32, data: Vec<f32>) -> Self {
+        assert_eq!(data.len(), (width * height * 3) as usize, "Image data size mismatch");
+        Self { width, height, data }
+    }
+
     /// Returns true if the image has no data.
     pub fn is_empty(&self) -> bool {
         self.data.is_empty()
@@ -69,7 +78,7 @@ impl ImageData {
     /// If the image data is not available, returns magenta.
     /// Coordinates are clamped to the image dimensions.
     /// Assumes (0,0) is the top-left corner and (width-1, height-1) is the bottom-right corner.
-    /// Returns color components in [0.0, 1.0].
+    /// Returns color components in [0.0, 1.0], or unbounded for HDR images.
     pub fn pixel_data(&self, x: u32, y: u32) -> Color {
         if self.is_empty() {
             return Color::new(1.0, 0.0, 1.0); // Magenta
@@ -87,6 +96,13 @@ impl ImageData {
     }
 }
 
+/// Returns true for high dynamic range formats whose data is stored linear.
+fn is_hdr_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("hdr") || ext.eq_ignore_ascii_case("exr"))
+}
+
 /// sRGB [0, 1] -> linear [0, 1] as f32.
 fn srgb_unit_to_linear(c: f32) -> f32 {
     if c <= 0.04045 {
@@ -0,0 +1,338 @@
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+use crate::prelude::*;
+
+// Realistic camera lenses traced element by element, after Kolb et al. and pbrt's RealisticCamera.
+// Lens space is in millimeters with the optical axis along z: the scene lies toward +z, the front
+// element's vertex is at the origin, and the film sits behind the rear element.
+
+// ----- Lens Prescription -----
+
+/// One spherical surface of a lens, listed from the front (scene side) to the rear.
+#[derive(Debug, Clone, Copy)]
+pub struct LensElement {
+    pub curvature_radius: f64, // Positive when convex toward the scene, 0 for the flat aperture stop
+    pub thickness: f64,        // Distance along the axis to the next surface
+    pub ior: f64,              // Refractive index behind the surface, 1 (or 0) for air
+    pub aperture_radius: f64,  // Radius of the clear aperture
+}
+
+/// A lens prescription with the film it projects onto.
+#[derive(Debug, Clone)]
+pub struct LensSystem {
+    elements: Vec<LensElement>,
+    film_diagonal: f64, // Sensor diagonal in millimeters
+    units_per_mm: f64,  // World units per millimeter, 0.001 for scenes modeled in meters
+}
+
+/// Classic double Gauss 50mm f/2 (US patent 2,673,491, scaled from 100mm), as radius, thickness,
+/// index and aperture diameter per surface.
+const DOUBLE_GAUSS_50MM: &str = "
+# radius  thickness  ior    aperture
+29.475    3.76       1.67   25.2
+84.83     0.12       1      25.2
+19.275    4.025      1.67   23
+40.77     3.275      1.699  23
+12.75     5.705      1      18
+0         4.5        0      17.1
+-14.495   1.18       1.603  17
+40.77     6.065      1.658  20
+-20.385   0.19       1      20
+437.065   3.22       1.717  20
+-39.73    0          1      20
+";
+
+impl LensSystem {
+    /// Constructor from elements listed front to rear, on a full-frame 35mm sensor in a scene
+    /// modeled in meters.
+    pub fn new(elements: Vec<LensElement>) -> Self {
+        assert!(!elements.is_empty(), "a lens needs at least one element");
+        Self { elements, film_diagonal: 43.27, units_per_mm: 0.001 }
+    }
+
+    /// The double Gauss 50mm f/2, a standard prime lens.
+    pub fn double_gauss_50mm() -> Self {
+        Self::parse(DOUBLE_GAUSS_50MM).expect("built-in lens prescription")
+    }
+
+    /// Load a lens prescription file, in the format used by pbrt: one surface per line, front to rear,
+    /// as curvature radius, thickness, refractive index and aperture diameter in millimeters.
+    /// A radius of 0 marks the aperture stop and lines starting with '#' are comments.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        println!("Loading lens file: {}", path.as_ref().display());
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse a lens prescription in the format of `load`.
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut elements = Vec::new();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let values: Vec<f64> = line.split_whitespace()
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<_, _>>()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, format!("Bad lens surface '{}': {}", line, e)))?;
+            let [curvature_radius, thickness, ior, aperture_diameter] = values[..] else {
+                return Err(Error::new(ErrorKind::InvalidData, format!("Lens surface needs 4 values: '{}'", line)));
+            };
+            elements.push(LensElement {
+                curvature_radius,
+                thickness,
+                ior: if ior == 0.0 { 1.0 } else { ior },
+                aperture_radius: 0.5 * aperture_diameter,
+            });
+        }
+        if elements.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "Lens prescription has no surfaces"));
+        }
+        Ok(Self::new(elements))
+    }
+
+    /// Set the sensor diagonal in millimeters, e.g. 43.27 for full frame or 28.4 for APS-C.
+    pub fn with_film_diagonal(mut self, film_diagonal: f64) -> Self {
+        self.film_diagonal = film_diagonal;
+        self
+    }
+
+    /// Set how many world units make a millimeter, e.g. 0.1 for a scene modeled in centimeters.
+    pub fn with_units_per_mm(mut self, units_per_mm: f64) -> Self {
+        self.units_per_mm = units_per_mm;
+        self
+    }
+
+    /// Stop the lens down (or open it up) by setting the aperture stop's diameter in millimeters.
+    pub fn with_aperture_stop(mut self, diameter: f64) -> Self {
+        for element in self.elements.iter_mut().filter(|element| element.curvature_radius == 0.0) {
+            element.aperture_radius = 0.5 * diameter;
+        }
+        self
+    }
+
+    /// World units per millimeter of lens space.
+    pub fn units_per_mm(&self) -> f64 {
+        self.units_per_mm
+    }
+
+    /// Focus at a distance in world units from the front element by moving the lens relative to the
+    /// film, for an image of the given aspect ratio. None if the lens cannot form an image there.
+    pub fn focus(&self, focus_dist: f64, aspect_ratio: f64) -> Option<FocusedLens> {
+        // A ray from the focused point on the axis, close to it, crosses the axis again where it is imaged
+        let distance = focus_dist / self.units_per_mm;
+        let height = 0.01 * self.elements[0].aperture_radius;
+        let (origin, direction) = self.trace(Point3::new(0.0, 0.0, distance), Vec3::new(height, 0.0, -distance), false)?;
+        if direction.x().abs() < EPSILON {
+            return None;
+        }
+        let image_z = origin.z() - origin.x() / direction.x() * direction.z();
+        let film_distance = self.rear_z() - image_z;
+        if film_distance <= 0.0 {
+            return None;
+        }
+
+        let film_height = self.film_diagonal / (1.0 + aspect_ratio * aspect_ratio).sqrt();
+        let mut lens = FocusedLens {
+            system: self.clone(),
+            film_z: self.rear_z() - film_distance,
+            film_width: film_height * aspect_ratio,
+            film_height,
+            pupil_bounds: Vec::new(),
+            reference_area: 0.0,
+        };
+        lens.compute_exit_pupils();
+        (lens.reference_area > 0.0).then_some(lens)
+    }
+
+    /// Working f-number at infinity focus, the effective focal length over the entrance pupil
+    /// diameter. None if no ray parallel to the axis gets through or is focused.
+    pub fn f_number(&self) -> Option<f64> {
+        let front_radius = self.elements[0].aperture_radius;
+        let parallel = |height: f64| self.trace(Point3::new(height, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0), false);
+
+        // Effective focal length from a paraxial ray: its height over the slope it leaves with
+        let height = 0.01 * front_radius;
+        let (_, direction) = parallel(height)?;
+        if direction.x().abs() < EPSILON {
+            return None;
+        }
+        let focal_length = height * direction.z().abs() / direction.x().abs();
+
+        // Entrance pupil radius: the highest parallel ray the aperture stop lets through
+        let (mut low, mut high) = (height, front_radius);
+        for _ in 0..40 {
+            let mid = 0.5 * (low + high);
+            if parallel(mid).is_some() { low = mid; } else { high = mid; }
+        }
+        Some(focal_length / (2.0 * low))
+    }
+
+    /// Position of the rear element's vertex on the axis.
+    fn rear_z(&self) -> f64 {
+        -self.elements[..self.elements.len() - 1].iter().map(|element| element.thickness).sum::<f64>()
+    }
+
+    /// Trace a ray through every surface, toward the scene from the film side or toward the film
+    /// from the scene side. Returns the ray leaving the last surface, or None if an aperture
+    /// blocks it or it reflects internally.
+    fn trace(&self, origin: Point3, direction: Vec3, toward_scene: bool) -> Option<(Point3, Vec3)> {
+        // Vertex positions, front to rear
+        let mut vertices = Vec::with_capacity(self.elements.len());
+        let mut z = 0.0;
+        for element in &self.elements {
+            vertices.push(z);
+            z -= element.thickness;
+        }
+
+        let mut origin = origin;
+        let mut direction = Vec3::unit_vector(&direction);
+        let order: Box<dyn Iterator<Item = usize>> = if toward_scene {
+            Box::new((0..self.elements.len()).rev())
+        } else {
+            Box::new(0..self.elements.len())
+        };
+        for k in order {
+            let element = &self.elements[k];
+            let vertex = vertices[k];
+
+            // Intersect the surface: a plane for the stop, else the cap of the sphere at the vertex
+            let (t, normal) = if element.curvature_radius == 0.0 {
+                if direction.z().abs() < EPSILON { return None; }
+                ((vertex - origin.z()) / direction.z(), Vec3::new(0.0, 0.0, 1.0))
+            } else {
+                let center = Point3::new(0.0, 0.0, vertex - element.curvature_radius);
+                let oc = origin - center;
+                let b = Vec3::dot(&oc, &direction);
+                let c = oc.length_squared() - element.curvature_radius * element.curvature_radius;
+                let discriminant = b * b - c;
+                if discriminant < 0.0 { return None; }
+                let root = discriminant.sqrt();
+                let t = [-b - root, -b + root].into_iter()
+                    .find(|&t| t > EPSILON && (origin.z() + t * direction.z() - center.z()) * element.curvature_radius > 0.0)?;
+                (t, Vec3::unit_vector(&(origin + t * direction - center)))
+            };
+            if t <= EPSILON { return None; }
+
+            let hit = origin + t * direction;
+            if hit.x() * hit.x() + hit.y() * hit.y() > element.aperture_radius * element.aperture_radius {
+                return None;
+            }
+            origin = hit;
+
+            // Refract from the medium the ray is in to the one on the other side of the surface
+            if element.curvature_radius != 0.0 {
+                let front_ior = if k == 0 { 1.0 } else { self.elements[k - 1].ior };
+                let (ior_in, ior_out) = if toward_scene { (element.ior, front_ior) } else { (front_ior, element.ior) };
+                let normal = if Vec3::dot(&direction, &normal) > 0.0 { -normal } else { normal };
+                let ratio = ior_in / ior_out;
+                let cos_theta = -Vec3::dot(&direction, &normal);
+                if ratio * ratio * (1.0 - cos_theta * cos_theta) > 1.0 {
+                    return None;
+                }
+                direction = Vec3::unit_vector(&Vec3::refract(&direction, &normal, ratio));
+            }
+        }
+        Some((origin, direction))
+    }
+}
+
+// ----- Focused Lens -----
+
+/// Number of film radii the exit pupil is precomputed for.
+const PUPIL_INTERVALS: usize = 64;
+
+/// Grid resolution used to find the exit pupil on the rear element.
+const PUPIL_GRID: usize = 48;
+
+/// A lens focused on its film, ready to generate camera rays.
+#[derive(Debug, Clone)]
+pub struct FocusedLens {
+    system: LensSystem,
+    film_z: f64,                // Film plane position on the axis
+    film_width: f64,
+    film_height: f64,
+    pupil_bounds: Vec<[f64; 4]>, // Region of the rear element light passes through, per film radius, as [x_min, x_max, y_min, y_max] for points on the +x axis
+    reference_area: f64,        // Effective exit pupil area seen from the film center
+}
+
+impl FocusedLens {
+    /// Generate a ray for a point on the image, in [0,1]^2 from the top-left corner. Returns the ray
+    /// leaving the front element in lens space, and its weight relative to the image center, which
+    /// falls off with the exit pupil's size and the angle to it (natural vignetting). None if the
+    /// lens blocks the ray (mechanical vignetting).
+    pub fn sample_ray(&self, s: f64, t: f64) -> Option<(Point3, Vec3, f64)> {
+        // The lens inverts the image, so the film is mirrored
+        let film = Point3::new(-(s - 0.5) * self.film_width, (t - 0.5) * self.film_height, self.film_z);
+        let radius = (film.x() * film.x() + film.y() * film.y()).sqrt();
+        let index = ((radius / self.max_film_radius() * PUPIL_INTERVALS as f64) as usize).min(PUPIL_INTERVALS - 1);
+        let [x_min, x_max, y_min, y_max] = self.pupil_bounds[index];
+        let area = (x_max - x_min) * (y_max - y_min);
+        if area <= 0.0 {
+            return None;
+        }
+
+        // Sample the pupil bounds, rotated from the +x axis to the film point
+        let (sin_phi, cos_phi) = if radius > 0.0 { (film.y() / radius, film.x() / radius) } else { (0.0, 1.0) };
+        let px = random_f64_range(x_min, x_max);
+        let py = random_f64_range(y_min, y_max);
+        let rear = Point3::new(cos_phi * px - sin_phi * py, sin_phi * px + cos_phi * py, self.system.rear_z());
+
+        let direction = Vec3::unit_vector(&(rear - film));
+        let (origin, direction_out) = self.system.trace(film, direction, true)?;
+        let cos_theta = direction.z();
+        let weight = area / self.reference_area * cos_theta.powi(4);
+        Some((origin, direction_out, weight))
+    }
+
+    /// World units per millimeter of lens space.
+    pub fn units_per_mm(&self) -> f64 {
+        self.system.units_per_mm
+    }
+
+    /// Working f-number of the lens, see `LensSystem::f_number`.
+    pub fn f_number(&self) -> Option<f64> {
+        self.system.f_number()
+    }
+
+    /// Half the film diagonal, the farthest film point from the axis.
+    fn max_film_radius(&self) -> f64 {
+        0.5 * (self.film_width * self.film_width + self.film_height * self.film_height).sqrt()
+    }
+
+    /// Find, for film points at increasing distance from the axis, the bounds of the rear element
+    /// region their light passes through, so rays are only sampled where they can get through.
+    fn compute_exit_pupils(&mut self) {
+        let rear_radius = self.system.elements.last().map_or(0.0, |element| element.aperture_radius);
+        let rear_z = self.system.rear_z();
+        let cell = 2.0 * rear_radius / PUPIL_GRID as f64;
+        let max_radius = self.max_film_radius();
+
+        self.pupil_bounds = (0..PUPIL_INTERVALS).map(|interval| {
+            let mut bounds = [f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY];
+            let radii = [interval as f64, interval as f64 + 0.5, interval as f64 + 1.0]
+                .map(|step| step / PUPIL_INTERVALS as f64 * max_radius);
+            let mut passed = 0;
+            for film_x in radii {
+                let film = Point3::new(film_x, 0.0, self.film_z);
+                for gy in 0..PUPIL_GRID {
+                    for gx in 0..PUPIL_GRID {
+                        let x = -rear_radius + (gx as f64 + 0.5) * cell;
+                        let y = -rear_radius + (gy as f64 + 0.5) * cell;
+                        let rear = Point3::new(x, y, rear_z);
+                        if self.system.trace(film, rear - film, true).is_some() {
+                            bounds = [bounds[0].min(x), bounds[1].max(x), bounds[2].min(y), bounds[3].max(y)];
+                            if interval == 0 && film_x == 0.0 { passed += 1; }
+                        }
+                    }
+                }
+            }
+            if interval == 0 {
+                self.reference_area = passed as f64 * cell * cell;
+            }
+            if bounds[0] > bounds[1] {
+                return [0.0; 4];
+            }
+            // Grow by a cell so the sparse grid does not clip the pupil
+            [bounds[0] - cell, bounds[1] + cell, bounds[2] - cell, bounds[3] + cell]
+        }).collect();
+    }
+}
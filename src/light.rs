@@ -0,0 +1,205 @@
+use std::f64::consts::PI;
+
+use crate::photometry::LightPower;
+use crate::prelude::*;
+
+// ----- Enum for different light types -----
+
+/// Delta light sources. These have no surface, so they are never hit by rays and can only
+/// contribute through explicit light sampling (next-event estimation) in the integrator.
+#[derive(Clone)]
+pub enum Light {
+    Point(PointLight),
+    Spot(SpotLight),
+    Directional(DirectionalLight),
+}
+
+/// Incident light arriving at a shading point from a light sample.
+pub struct LightSample {
+    pub direction: Vec3, // Unit direction from the shading point towards the light
+    pub distance: f64,   // Distance to the light, infinite for directional lights
+    pub radiance: Color, // Incident radiance, already including the inverse-square falloff
+}
+
+impl Light {
+    /// Sample the light as seen from `point`. Returns None when the point receives no light.
+    pub fn sample(&self, point: &Point3) -> Option<LightSample> {
+        match self {
+            Light::Point(light) => light.sample(point),
+            Light::Spot(light) => light.sample(point),
+            Light::Directional(light) => light.sample(point),
+        }
+    }
+
+    // Convenience constructors
+
+    /// Create a point light at `position` with radiant intensity `intensity` (W/sr).
+    pub fn point(position: Point3, intensity: Color) -> Self {
+        Self::Point(PointLight::new(position, intensity))
+    }
+    /// Create a spotlight at `position` aimed at `target`, with full intensity inside `inner_angle`
+    /// and falling off to zero at `outer_angle` (both half-angles in degrees).
+    pub fn spot(position: Point3, target: Point3, intensity: Color, inner_angle: f64, outer_angle: f64) -> Self {
+        Self::Spot(SpotLight::new(position, target, intensity, inner_angle, outer_angle))
+    }
+    /// Create a directional (sun) light shining along `direction` with irradiance `irradiance` (W/m^2).
+    pub fn directional(direction: Vec3, irradiance: Color) -> Self {
+        Self::Directional(DirectionalLight::new(direction, irradiance))
+    }
+}
+
+// From implementations for each light type
+impl From<PointLight> for Light {
+    fn from(light: PointLight) -> Self { Light::Point(light) }
+}
+impl From<SpotLight> for Light {
+    fn from(light: SpotLight) -> Self { Light::Spot(light) }
+}
+impl From<DirectionalLight> for Light {
+    fn from(light: DirectionalLight) -> Self { Light::Directional(light) }
+}
+
+// ----- Point Light -----
+
+/// An isotropic point light.
+#[derive(Clone)]
+pub struct PointLight {
+    position: Point3,
+    intensity: Color, // Radiant intensity in W/sr
+}
+
+impl PointLight {
+    /// Constructor from a position and radiant intensity.
+    pub fn new(position: Point3, intensity: Color) -> Self {
+        Self { position, intensity }
+    }
+
+    /// Constructor from a position, a color used as chromaticity, and a total power in watts or lumens.
+    pub fn with_power(position: Point3, color: Color, power: LightPower) -> Self {
+        Self::new(position, intensity_from_power(color, power, 4.0 * PI))
+    }
+
+    #[inline]
+    fn sample(&self, point: &Point3) -> Option<LightSample> {
+        let to_light = self.position - *point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= EPSILON {
+            return None;
+        }
+        let distance = distance_squared.sqrt();
+        Some(LightSample {
+            direction: to_light / distance,
+            distance,
+            radiance: self.intensity / distance_squared,
+        })
+    }
+}
+
+// ----- Spot Light -----
+
+/// A point light restricted to a cone, with a smooth falloff between an inner and outer angle.
+#[derive(Clone)]
+pub struct SpotLight {
+    position: Point3,
+    axis: Vec3,       // Unit direction the spotlight points in
+    intensity: Color, // Radiant intensity along the axis in W/sr
+    cos_inner: f64,   // Cosine of the half-angle where falloff starts
+    cos_outer: f64,   // Cosine of the half-angle where the light is fully off
+}
+
+impl SpotLight {
+    /// Constructor from a position, target point, intensity, and inner/outer cone half-angles in degrees.
+    pub fn new(position: Point3, target: Point3, intensity: Color, inner_angle: f64, outer_angle: f64) -> Self {
+        let outer = outer_angle.clamp(0.0, 90.0);
+        let inner = inner_angle.clamp(0.0, outer);
+        Self {
+            position,
+            axis: Vec3::unit_vector(&(target - position)),
+            intensity,
+            cos_inner: inner.to_radians().cos(),
+            cos_outer: outer.to_radians().cos(),
+        }
+    }
+
+    /// Constructor from a total power in watts or lumens, spread over the cone's solid angle.
+    pub fn with_power(position: Point3, target: Point3, color: Color, power: LightPower, inner_angle: f64, outer_angle: f64) -> Self {
+        let mut light = Self::new(position, target, color, inner_angle, outer_angle);
+        // Solid angle of the cone, counting the falloff region at half weight
+        let solid_angle = 2.0 * PI * (1.0 - 0.5 * (light.cos_inner + light.cos_outer));
+        light.intensity = intensity_from_power(color, power, solid_angle);
+        light
+    }
+
+    /// Smoothstep falloff from the outer to the inner cone.
+    #[inline]
+    fn falloff(&self, cos_theta: f64) -> f64 {
+        if cos_theta >= self.cos_inner {
+            return 1.0;
+        }
+        if cos_theta <= self.cos_outer {
+            return 0.0;
+        }
+        let t = (cos_theta - self.cos_outer) / (self.cos_inner - self.cos_outer);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    #[inline]
+    fn sample(&self, point: &Point3) -> Option<LightSample> {
+        let to_light = self.position - *point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= EPSILON {
+            return None;
+        }
+        let distance = distance_squared.sqrt();
+        let direction = to_light / distance;
+
+        let falloff = self.falloff(Vec3::dot(&-direction, &self.axis));
+        if falloff <= 0.0 {
+            return None;
+        }
+        Some(LightSample {
+            direction,
+            distance,
+            radiance: falloff * self.intensity / distance_squared,
+        })
+    }
+}
+
+// ----- Directional Light -----
+
+/// A light infinitely far away, such as the sun, arriving from a single direction.
+#[derive(Clone)]
+pub struct DirectionalLight {
+    to_light: Vec3,    // Unit direction pointing back towards the light
+    irradiance: Color, // Irradiance on a surface facing the light in W/m^2
+}
+
+impl DirectionalLight {
+    /// Constructor from the direction the light travels in and its irradiance.
+    pub fn new(direction: Vec3, irradiance: Color) -> Self {
+        Self {
+            to_light: -Vec3::unit_vector(&direction),
+            irradiance,
+        }
+    }
+
+    #[inline]
+    fn sample(&self, _point: &Point3) -> Option<LightSample> {
+        Some(LightSample {
+            direction: self.to_light,
+            distance: f64::INFINITY,
+            radiance: self.irradiance,
+        })
+    }
+}
+
+/// Radiant intensity (W/sr) of a light with the given chromaticity emitting `power` over `solid_angle`.
+/// `LightPower::Nits` has no meaning for a light without a surface and yields no light.
+#[inline]
+fn intensity_from_power(color: Color, power: LightPower, solid_angle: f64) -> Color {
+    let luminance = color.luminance();
+    match power.watts() {
+        Some(watts) if luminance > 0.0 && solid_angle > 0.0 => (watts / solid_angle) * color / luminance,
+        _ => Color::zero(),
+    }
+}
@@ -1,28 +1,58 @@
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 
+mod animation;
+mod aperture;
+mod background;
 mod camera;
 mod color;
+mod distribution;
+mod environment;
 mod hittable;
 mod image_data;
 mod interval;
+mod lens;
+mod light;
 mod material;
+mod medium;
+mod microfacet;
 mod prelude;
+mod projection;
 mod ray;
+mod sky;
 mod texture;
+mod transform;
 mod vec3;
+mod volume;
 mod noise;
 mod onb;
 mod pdf;
+mod phase;
+mod photometry;
+mod physical_camera;
 mod ply;
+mod vol;
 
 use std::sync::Arc;
 
 use crate::prelude::*;
+use crate::animation::{CameraKeyframe, CameraPath, Sequence};
+use crate::aperture::Aperture;
+use crate::background::Background;
 use crate::camera::Camera;
+use crate::environment::EnvironmentMap;
 use crate::hittable::*;
+use crate::lens::LensSystem;
+use crate::light::*;
 use crate::material::*;
+use crate::medium::Medium;
+use crate::sky::Sky;
+use crate::photometry::LightPower;
+use crate::physical_camera::PhysicalCamera;
+use crate::projection::{Projection, Stereo};
 use crate::texture::*;
+use crate::transform::{AnimatedTransform, Keyframe, Transform};
+use crate::volume::*;
 
 fn bouncing_spheres() {
     let mut world = HittableList::new();
@@ -174,7 +204,7 @@ fn simple_light() {
 
     let mut cam = Camera::default();
 
-    cam.background = Color::zero();
+    cam.background = Background::solid(Color::zero());
 
     cam.v_fov = 20.0;
     cam.look_from = Point3::new(26.0, 3.0, 6.0);
@@ -234,7 +264,7 @@ fn cornell_box() {
     cam.image_width = 600;
     cam.samples_per_pixel = 100;
     cam.max_depth = 50;
-    cam.background = Color::new(0.0, 0.0, 0.0);
+    cam.background = Background::solid(Color::new(0.0, 0.0, 0.0));
 
     cam.v_fov = 40.0;
     cam.look_from = Point3::new(278.0, 278.0, -800.0);
@@ -292,7 +322,7 @@ fn cornell_smoke() {
     cam.image_width = 600;
     cam.samples_per_pixel = 4000;
     cam.max_depth = 50;
-    cam.background = Color::new(0.0, 0.0, 0.0);
+    cam.background = Background::solid(Color::new(0.0, 0.0, 0.0));
 
     cam.v_fov = 40.0;
     cam.look_from = Point3::new(278.0, 278.0, -800.0);
@@ -426,7 +456,7 @@ fn final_scene(image_width: u32, samples_per_pixel: u32, max_depth: u32) {
     cam.image_width = image_width;
     cam.samples_per_pixel = samples_per_pixel;
     cam.max_depth = max_depth;
-    cam.background = Color::new(0.0, 0.0, 0.0);
+    cam.background = Background::solid(Color::new(0.0, 0.0, 0.0));
 
     cam.v_fov = 40.0;
     cam.look_from = Point3::new(478.0, 278.0, -600.0);
@@ -492,7 +522,7 @@ fn pyramid() {
     cam.image_width = 1024;
     cam.samples_per_pixel = 3000;
     cam.max_depth = 50;
-    cam.background = Color::new(0.15, 0.15, 0.2);
+    cam.background = Background::solid(Color::new(0.15, 0.15, 0.2));
 
     cam.v_fov = 45.0;
     cam.look_from = Point3::new(4.5, 3.0, 4.5);
@@ -548,7 +578,7 @@ fn ply_model_scene() {
     cam.image_width = 1024;
     cam.samples_per_pixel = 500;
     cam.max_depth = 20;
-    cam.background = Color::new(0.02, 0.02, 0.03);
+    cam.background = Background::solid(Color::new(0.02, 0.02, 0.03));
 
     cam.v_fov = 20.0;
     // cam.v_fov = 5.0;
@@ -597,37 +627,1151 @@ fn ply_model_spin() {
 
     let sampling_target = Arc::new(Hittable::Quad(light_quad));
 
-    // --- Render frames ---
-    for frame in 0..frames {
-        let angle = (frame as f64 / frames as f64) * 360.0;
-        let dragon = Hittable::rotate_y_translate(
-            base_mesh.clone(),
-            (180.0 - 35.0) + angle,
-            Vec3::new(-0.1, -0.3, -1.0),
-        );
-
-        let mut world = world_base.clone();
-        world.add(dragon);
-
-        let mut cam = Camera::default();
-        cam.scene_name = format!("dragon_spin_{:03}", frame);
-        cam.append_data = false;
-
-        cam.aspect_ratio = 1.0;
-        cam.image_width = 1024;
-        cam.samples_per_pixel = 500;
-        cam.max_depth = 20;
-        cam.background = Color::new(0.02, 0.02, 0.03);
-
-        cam.v_fov = 20.0;
-        cam.look_from = Point3::new(0.0, 1.5, 4.0);
-        cam.look_at = Point3::new(-0.1, 0.75, 0.0);
-        cam.v_up = Vec3::new(0.0, 1.0, 0.0);
-        cam.aperture_angle = 0.0;
-
-        let world = world.into_bvh();
-        cam.render(world, Some(sampling_target.clone()));
+    // One turn over the sequence, keyed every quarter turn. The world and its BVH are built once.
+    let sequence = Sequence::new(frames, 30.0).with_output_dir("dragon_spin");
+    let duration = frames as f64 / sequence.frame_rate;
+    let spin = AnimatedTransform::new((0..=4).map(|i| {
+        Keyframe::new(duration * i as f64 / 4.0)
+            .with_rotation(Vec3::new(0.0, 1.0, 0.0), (180.0 - 35.0) + 90.0 * i as f64)
+            .with_translation(Vec3::new(-0.1, -0.3, -1.0))
+    }).collect());
+    world_base.add(Hittable::animated(Hittable::from(base_mesh), spin));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "dragon_spin".to_string();
+
+    cam.aspect_ratio = 1.0;
+    cam.image_width = 1024;
+    cam.samples_per_pixel = 500;
+    cam.max_depth = 20;
+    cam.background = Background::solid(Color::new(0.02, 0.02, 0.03));
+
+    cam.v_fov = 20.0;
+    cam.look_from = Point3::new(0.0, 1.5, 4.0);
+    cam.look_at = Point3::new(-0.1, 0.75, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    cam.render_sequence(world_base.into_bvh(), Some(sampling_target), &sequence);
+}
+
+fn principled_spheres() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian_tex(Texture::checker(0.5, Color::new(0.2, 0.2, 0.2), Color::new(0.8, 0.8, 0.8)));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // A row of spheres spanning the principled parameter space
+    let plastic = Principled::new(Color::new(0.8, 0.1, 0.1))
+        .with_roughness(Texture::scalar(0.3));
+    let gold = Principled::new(Color::new(1.0, 0.78, 0.34))
+        .with_metallic(Texture::scalar(1.0))
+        .with_roughness(Texture::scalar(0.35));
+    let glass = Principled::new(Color::new(0.9, 1.0, 0.95))
+        .with_transmission(Texture::scalar(1.0))
+        .with_roughness(Texture::scalar(0.05));
+    let velvet = Principled::new(Color::new(0.25, 0.05, 0.4))
+        .with_roughness(Texture::scalar(1.0))
+        .with_sheen(Texture::scalar(1.0))
+        .with_subsurface(Texture::scalar(0.5));
+    let car_paint = Principled::new(Color::new(0.05, 0.15, 0.6))
+        .with_metallic(Texture::scalar(0.5))
+        .with_roughness(Texture::scalar(0.4))
+        .with_clearcoat(Texture::scalar(1.0));
+
+    let materials: [Arc<Material>; 5] = [plastic.into(), gold.into(), glass.into(), velvet.into(), car_paint.into()];
+    for (i, material) in materials.into_iter().enumerate() {
+        let x = -4.0 + 2.0 * i as f64;
+        world.add(Sphere::new(&Point3::new(x, 0.8, 0.0), 0.8, material));
+    }
+
+    let light = Material::diffuse_light(Color::new(6.0, 6.0, 6.0));
+    let light_quad = Quad::new(&Point3::new(-3.0, 6.0, -3.0), &Vec3::new(6.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 6.0), light);
+    world.add(light_quad.clone());
+
+    let mut cam = Camera::default();
+    cam.scene_name = "principled_spheres".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.05, 0.05, 0.08));
+
+    cam.v_fov = 30.0;
+    cam.look_from = Point3::new(0.0, 3.0, 14.0);
+    cam.look_at = Point3::new(0.0, 0.8, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::Quad(light_quad));
+    cam.render(world, Some(sampling_target));
+}
+
+fn coated_spheres() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian_tex(Texture::checker(0.5, Color::new(0.2, 0.2, 0.2), Color::new(0.8, 0.8, 0.8)));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // Varnished "wood": an amber-tinted coat over a noise textured diffuse base
+    let wood_base = Material::lambertian_tex(Texture::noise(6.0));
+    let varnish = Coated::new(wood_base, 1.5)
+        .with_absorption(Color::new(0.1, 0.4, 1.2), 0.5);
+
+    // Car paint: a clear coat over a rough blue conductor
+    let paint_base = Material::metal(Color::new(0.1, 0.2, 0.7), 0.4);
+    let car_paint = Coated::new(paint_base, 1.5);
+
+    // Glossy plastic: a slightly rough coat over a saturated diffuse base
+    let plastic_base = Material::lambertian(Color::new(0.1, 0.6, 0.1));
+    let plastic = Coated::new(plastic_base, 1.5).with_roughness(0.2);
+
+    let materials: [Arc<Material>; 3] = [varnish.into(), car_paint.into(), plastic.into()];
+    for (i, material) in materials.into_iter().enumerate() {
+        let x = -2.5 + 2.5 * i as f64;
+        world.add(Sphere::new(&Point3::new(x, 1.0, 0.0), 1.0, material));
+    }
+
+    let light = Material::diffuse_light(Color::new(6.0, 6.0, 6.0));
+    let light_quad = Quad::new(&Point3::new(-3.0, 6.0, -3.0), &Vec3::new(6.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 6.0), light);
+    world.add(light_quad.clone());
+
+    let mut cam = Camera::default();
+    cam.scene_name = "coated_spheres".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.05, 0.05, 0.08));
+
+    cam.v_fov = 30.0;
+    cam.look_from = Point3::new(0.0, 3.0, 12.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::Quad(light_quad));
+    cam.render(world, Some(sampling_target));
+}
+
+fn rough_diffuse() {
+    let mut world = HittableList::new();
+
+    let ground = Material::oren_nayar(Color::new(0.5, 0.5, 0.5), 30.0);
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // Lambertian next to increasingly rough Oren-Nayar spheres
+    world.add(Sphere::new(&Point3::new(-3.0, 1.0, 0.0), 1.0, Material::lambertian(Color::new(0.8, 0.6, 0.4))));
+    world.add(Sphere::new(&Point3::new(0.0, 1.0, 0.0), 1.0, Material::oren_nayar(Color::new(0.8, 0.6, 0.4), 20.0)));
+    world.add(Sphere::new(&Point3::new(3.0, 1.0, 0.0), 1.0, Material::oren_nayar(Color::new(0.8, 0.6, 0.4), 60.0)));
+
+    // Backlit translucent "leaf"
+    let leaf = Material::translucent(Color::new(0.3, 0.7, 0.2), 0.6);
+    world.add(Quad::new(&Point3::new(-1.0, 2.5, -2.0), &Vec3::new(2.0, 0.0, 0.0), &Vec3::new(0.0, 1.5, 0.0), leaf));
+
+    let light = Material::diffuse_light(Color::new(8.0, 8.0, 8.0));
+    let light_quad = Quad::new(&Point3::new(-1.0, 5.0, -6.0), &Vec3::new(2.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), light);
+    world.add(light_quad.clone());
+
+    let mut cam = Camera::default();
+    cam.scene_name = "rough_diffuse".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.05, 0.05, 0.08));
+
+    cam.v_fov = 35.0;
+    cam.look_from = Point3::new(0.0, 3.0, 12.0);
+    cam.look_at = Point3::new(0.0, 1.5, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::Quad(light_quad));
+    cam.render(world, Some(sampling_target));
+}
+
+fn emission_modes() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.6, 0.6, 0.6));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    world.add(Sphere::new(&Point3::new(-2.5, 1.0, 0.0), 1.0, Material::lambertian(Color::new(0.8, 0.3, 0.3))));
+    world.add(Sphere::new(&Point3::new(2.5, 1.0, 0.0), 1.0, Material::lambertian(Color::new(0.3, 0.3, 0.8))));
+
+    let mut lights = HittableList::new();
+
+    // Double-sided vertical panel lighting both spheres
+    let panel: Arc<Material> = DiffuseLight::new(Color::new(1.0, 0.9, 0.7))
+        .with_sides(EmissionSides::Both)
+        .with_intensity(6.0)
+        .into();
+    let panel_quad = Quad::new(&Point3::new(0.0, 0.2, -1.0), &Vec3::new(0.0, 0.0, 2.0), &Vec3::new(0.0, 2.0, 0.0), panel);
+    world.add(panel_quad.clone());
+    lights.add(panel_quad);
+
+    // Focused ceiling light pooling onto the ground in front of the spheres
+    let spot: Arc<Material> = DiffuseLight::new(Color::new(0.7, 0.8, 1.0))
+        .with_intensity(10.0)
+        .with_focus(40.0)
+        .into();
+    let spot_quad = Quad::new(&Point3::new(-0.5, 6.0, 2.5), &Vec3::new(1.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, -1.0), spot);
+    world.add(spot_quad.clone());
+    lights.add(spot_quad);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "emission_modes".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.02, 0.02, 0.03));
+
+    cam.v_fov = 35.0;
+    cam.look_from = Point3::new(0.0, 3.0, 12.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world, Some(sampling_target));
+}
+
+fn blackbody_lights() {
+    let mut world = HittableList::new();
+
+    let white = Material::lambertian(Color::new(0.73, 0.73, 0.73));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), white.clone()));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -3.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 10.0, 0.0), white.clone()));
+
+    // Candle, tungsten and daylight temperatures at the same luminous power
+    let mut lights = HittableList::new();
+    for (i, kelvin) in [1900.0, 3200.0, 6500.0].into_iter().enumerate() {
+        let x = -3.5 + 3.0 * i as f64;
+        world.add(Sphere::new(&Point3::new(x + 0.5, 0.5, 0.0), 0.5, white.clone()));
+
+        let panel = Quad::new(&Point3::new(x, 3.0, -0.5), &Vec3::new(1.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 1.0), white.clone())
+            .with_emitter(DiffuseLight::blackbody(kelvin).with_sides(EmissionSides::Both), LightPower::Lumens(40000.0));
+        world.add(panel.clone());
+        lights.add(panel);
     }
+
+    let mut cam = Camera::default();
+    cam.scene_name = "blackbody_lights".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.0, 0.0, 0.0));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.0, 10.0);
+    cam.look_at = Point3::new(0.0, 1.5, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world, Some(sampling_target));
+}
+
+fn delta_lights() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.6, 0.6, 0.6));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    world.add(Sphere::new(&Point3::new(-2.5, 1.0, 0.0), 1.0, Material::oren_nayar(Color::new(0.8, 0.8, 0.8), 20.0)));
+    world.add(Sphere::new(&Point3::new(0.0, 1.0, 0.0), 1.0, Material::metal(Color::new(0.9, 0.8, 0.6), 0.3)));
+    world.add(Sphere::new(&Point3::new(2.5, 1.0, 0.0), 1.0, Material::lambertian(Color::new(0.3, 0.5, 0.8))));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "delta_lights".to_string();
+
+    // Warm point light, a cool spotlight on the right sphere, and a dim low sun for long shadows
+    cam.lights.push(Light::from(PointLight::with_power(Point3::new(-3.0, 3.5, 2.0), Color::new(1.0, 0.8, 0.6), LightPower::Watts(150.0))));
+    cam.lights.push(Light::spot(Point3::new(4.0, 5.0, 3.0), Point3::new(2.5, 0.5, 0.0), Color::new(20.0, 25.0, 40.0), 10.0, 20.0));
+    cam.lights.push(Light::directional(Vec3::new(-1.0, -0.4, -0.6), Color::new(0.4, 0.35, 0.3)));
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 100;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.01, 0.01, 0.02));
+
+    cam.v_fov = 35.0;
+    cam.look_from = Point3::new(0.0, 3.0, 12.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    // Delta lights need no sampling target, they are sampled explicitly by the camera
+    cam.render(world.into_bvh(), None);
+}
+
+fn environment_lighting() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.5, 0.5, 0.5));
+    world.add(Quad::new(&Point3::new(-20.0, 0.0, -20.0), &Vec3::new(40.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 40.0), ground));
+
+    world.add(Sphere::new(&Point3::new(-2.5, 1.0, 0.0), 1.0, Material::lambertian(Color::new(0.8, 0.3, 0.3))));
+    world.add(Sphere::new(&Point3::new(0.0, 1.0, 0.0), 1.0, Material::dielectric(1.5)));
+    world.add(Sphere::new(&Point3::new(2.5, 1.0, 0.0), 1.0, Material::metal(Color::new(0.8, 0.8, 0.9), 0.05)));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "environment_lighting".to_string();
+
+    // Equirectangular HDR image, searched for in textures/ like image textures
+    let environment = EnvironmentMap::new("environment.hdr")
+        .with_rotation(30.0)
+        .with_intensity(1.0);
+    cam.background = Background::environment(environment);
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 30;
+
+    cam.v_fov = 35.0;
+    cam.look_from = Point3::new(0.0, 2.5, 12.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    // The environment is importance sampled on its own, no sampling target needed
+    cam.render(world.into_bvh(), None);
+}
+
+fn sky_scene() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.4, 0.4, 0.35));
+    world.add(Quad::new(&Point3::new(-50.0, 0.0, -50.0), &Vec3::new(100.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 100.0), ground));
+
+    // A simple block building with a glass and a metal sphere in front of it
+    let concrete = Material::oren_nayar(Color::new(0.7, 0.68, 0.65), 20.0);
+    world.add(Cuboid::new(&Point3::new(-4.0, 0.0, -4.0), &Point3::new(1.0, 3.0, -1.5), concrete.clone()));
+    world.add(Cuboid::new(&Point3::new(1.5, 0.0, -5.0), &Point3::new(4.0, 5.0, -2.5), concrete));
+    world.add(Sphere::new(&Point3::new(-1.0, 1.0, 1.0), 1.0, Material::dielectric(1.5)));
+    world.add(Sphere::new(&Point3::new(1.5, 1.0, 1.5), 1.0, Material::metal(Color::new(0.9, 0.9, 0.9), 0.0)));
+
+    // Late afternoon sun in a clear sky, scaled down to a displayable range
+    let sky = Sky::new(25.0, 240.0).with_turbidity(3.0);
+    let mut cam = Camera::default();
+    cam.scene_name = "sky".to_string();
+    cam.background = Background::environment(sky.to_environment(1024, 512).with_intensity(0.04));
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 30;
+
+    cam.v_fov = 45.0;
+    cam.look_from = Point3::new(2.0, 2.0, 12.0);
+    cam.look_at = Point3::new(0.0, 2.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    cam.render(world.into_bvh(), None);
+}
+
+fn backplate() {
+    let mut world = HittableList::new();
+
+    world.add(Sphere::new(&Point3::new(-1.2, 0.0, 0.0), 1.0, Material::lambertian(Color::new(0.8, 0.8, 0.8))));
+    world.add(Sphere::new(&Point3::new(1.2, 0.0, 0.0), 1.0, Material::dielectric(1.5)));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "backplate".to_string();
+
+    // The camera sees a photo behind the objects, while a soft sky gradient lights them
+    cam.background = Background::plate("earthmap.jpg");
+    cam.lighting = Some(Background::gradient(Color::new(0.3, 0.25, 0.2), Color::new(0.6, 0.75, 1.0)));
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 100;
+    cam.max_depth = 30;
+
+    cam.v_fov = 30.0;
+    cam.look_from = Point3::new(0.0, 0.0, 9.0);
+    cam.look_at = Point3::new(0.0, 0.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    cam.render(world.into_bvh(), None);
+}
+
+fn textured_emitters() {
+    let mut world = HittableList::new();
+
+    let white = Material::lambertian(Color::new(0.73, 0.73, 0.73));
+    world.add(Quad::new(&Point3::new(-6.0, 0.0, -6.0), &Vec3::new(12.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 12.0), white.clone()));
+    world.add(Quad::new(&Point3::new(-6.0, 0.0, -3.0), &Vec3::new(12.0, 0.0, 0.0), &Vec3::new(0.0, 6.0, 0.0), white.clone()));
+    world.add(Sphere::new(&Point3::new(0.0, 0.8, 0.5), 0.8, Material::lambertian(Color::new(0.8, 0.8, 0.8))));
+
+    let mut lights = HittableList::new();
+
+    // "TV screen": a checkered emissive quad, light sampled towards its bright squares
+    let screen_tex = Texture::checker(0.5, Color::new(8.0, 7.0, 6.0), Color::new(0.0, 0.0, 0.0));
+    let screen = Quad::new(&Point3::new(-4.0, 0.5, -1.5), &Vec3::new(2.5, 0.0, 0.0), &Vec3::new(0.0, 1.5, 0.0), Material::diffuse_light_tex(screen_tex));
+    world.add(screen.clone());
+    lights.add(screen);
+
+    // "Stained glass" window: a mesh with UVs mapping an image across its two triangles
+    let positions = vec![
+        Point3::new(1.5, 1.5, -1.5), Point3::new(4.0, 1.5, -1.5),
+        Point3::new(4.0, 4.0, -1.5), Point3::new(1.5, 4.0, -1.5),
+    ];
+    let uvs = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    let window_light = DiffuseLight::from_texture(Texture::image("earthmap.jpg")).with_intensity(8.0);
+    let window = TriangleMesh::new_with_uvs(positions, vec![[0, 1, 2], [0, 2, 3]], uvs, window_light.into(), false);
+    world.add(window.clone());
+    lights.add(window);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "textured_emitters".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.0, 0.0, 0.0));
+
+    cam.v_fov = 45.0;
+    cam.look_from = Point3::new(0.0, 2.5, 8.0);
+    cam.look_at = Point3::new(0.0, 2.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world, Some(sampling_target));
+}
+
+fn volume_clouds() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.45, 0.5, 0.35));
+    world.add(Quad::new(&Point3::new(-50.0, 0.0, -50.0), &Vec3::new(100.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 100.0), ground));
+
+    // Cloud: turbulence baked into a voxel grid, faded out towards an ellipsoid's edge
+    let cloud_min = Point3::new(-4.0, 3.0, -3.0);
+    let cloud_max = Point3::new(2.0, 6.0, 1.0);
+    let turbulence = NoiseDensity::new(5.0, 5);
+    let cloud_grid = VoxelGrid::from_fn(96, 48, 64, AABB::from_corners(&cloud_min, &cloud_max), |p| {
+        let offset = *p - Point3::new(0.5, 0.5, 0.5);
+        let falloff = (1.0 - 4.0 * offset.length_squared()).max(0.0);
+        falloff * turbulence.density(p)
+    });
+    let cloud_boundary = Arc::new(Hittable::from(Cuboid::new(&cloud_min, &cloud_max, Material::lambertian(Color::zero()))));
+    world.add(HeterogeneousMedium::new(cloud_boundary, cloud_grid, 12.0, &Color::new(0.95, 0.95, 0.95)));
+
+    // Smoke: procedural turbulence with a threshold that breaks it into wisps
+    let smoke_boundary = Arc::new(Hittable::from(Sphere::new(&Point3::new(3.0, 1.5, 0.0), 1.5, Material::lambertian(Color::zero()))));
+    let smoke = NoiseDensity::new(1.5, 4).with_threshold(0.2);
+    world.add(HeterogeneousMedium::new(smoke_boundary, smoke, 6.0, &Color::new(0.6, 0.6, 0.6)));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "volume_clouds".to_string();
+
+    // Sunlight shadows through the media are estimated with ratio tracking
+    cam.lights.push(Light::directional(Vec3::new(-0.5, -1.0, -0.3), Color::new(2.5, 2.4, 2.2)));
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 30;
+    cam.background = Background::gradient(Color::new(0.7, 0.75, 0.8), Color::new(0.3, 0.5, 0.9));
+
+    cam.v_fov = 45.0;
+    cam.look_from = Point3::new(0.0, 2.5, 12.0);
+    cam.look_at = Point3::new(0.0, 3.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    cam.render(world.into_bvh(), None);
+}
+
+fn volume_file() {
+    // Load a dense grid (e.g. exported from Mitsuba or converted from VDB) and place it in the world
+    let data = vol::load("models/smoke.vol").expect("load .vol");
+    let grid = VoxelGrid::from(data).with_bounds(AABB::from_corners(&Point3::new(-1.5, 0.0, -1.5), &Point3::new(1.5, 4.0, 1.5)));
+    let smoke = HeterogeneousMedium::from_grid(grid, 20.0, &Color::new(0.8, 0.8, 0.8));
+
+    let mut world = HittableList::new();
+    world.add(Hittable::rotate_y(smoke, 30.0));
+
+    let ground = Material::lambertian(Color::new(0.4, 0.4, 0.4));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "volume_file".to_string();
+    cam.lights.push(Light::from(PointLight::with_power(Point3::new(3.0, 6.0, 4.0), Color::new(1.0, 0.9, 0.8), LightPower::Watts(800.0))));
+
+    cam.aspect_ratio = 1.0;
+    cam.image_width = 600;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 30;
+    cam.background = Background::gradient(Color::new(0.05, 0.05, 0.06), Color::new(0.1, 0.12, 0.18));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.0, 9.0);
+    cam.look_at = Point3::new(0.0, 2.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    cam.render(world.into_bvh(), None);
+}
+
+fn anisotropic_media() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.3, 0.3, 0.3));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // Backlit smoke balls: isotropic, forward-scattering HG, and double HG with some back-scattering.
+    // Forward scattering glows where the light shines through and looks dark from the lit side.
+    let white = Color::new(0.9, 0.9, 0.9);
+    let phases = [
+        Material::isotropic(white),
+        Material::henyey_greenstein(white, 0.8),
+        Material::double_henyey_greenstein(white, 0.85, -0.4, 0.8),
+    ];
+    for (i, phase) in phases.into_iter().enumerate() {
+        let center = Point3::new(-2.6 + 2.6 * i as f64, 1.2, 0.0);
+        let boundary = Arc::new(Hittable::from(Sphere::new(&center, 1.1, phase.clone())));
+        world.add(ConstantMedium::new(boundary, 1.5, &white).with_phase_function(phase));
+    }
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-5.0, 0.5, -4.0), &Vec3::new(10.0, 0.0, 0.0), &Vec3::new(0.0, 3.0, 0.0), Material::diffuse_light(Color::new(4.0, 4.0, 4.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "anisotropic_media".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 30;
+    cam.background = Background::solid(Color::new(0.02, 0.02, 0.03));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.0, 10.0);
+    cam.look_at = Point3::new(0.0, 1.2, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn chromatic_media() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.5, 0.5, 0.5));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // Wine: absorbs green and blue strongly, so thick paths turn deep red
+    let wine = Arc::new(Hittable::from(Sphere::new(&Point3::new(-2.6, 1.1, 0.0), 1.1, Material::default().into())));
+    world.add(ConstantMedium::chromatic(wine, Color::new(0.3, 3.0, 2.5), Color::new(0.2, 0.2, 0.2)));
+
+    // Rayleigh-like haze: scatters blue more than red, blue from the side and orange seen through
+    let haze = Arc::new(Hittable::from(Sphere::new(&Point3::new(0.0, 1.1, 0.0), 1.1, Material::default().into())));
+    world.add(ConstantMedium::chromatic(haze, Color::zero(), Color::new(0.4, 1.0, 2.5)));
+
+    // Fire: hot absorbing gas glowing with a blackbody color
+    let fire = Arc::new(Hittable::from(Sphere::new(&Point3::new(2.6, 1.1, 0.0), 1.1, Material::default().into())));
+    let flame = 2.0 * photometry::blackbody(1800.0);
+    world.add(ConstantMedium::chromatic(fire, Color::new(1.5, 1.5, 1.5), Color::new(0.3, 0.3, 0.3)).with_emission(flame));
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 5.0, -1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(6.0, 6.0, 6.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "chromatic_media".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.05, 0.05, 0.07));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.5, 10.0);
+    cam.look_at = Point3::new(0.0, 1.1, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn interior_media() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.5, 0.5, 0.5));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // Glass globe filled with juice: the liquid has the higher priority, so it replaces the glass
+    // inside it and refracts against the glass rather than air
+    let glass = Arc::new(Material::Interior(Interior::new(Material::dielectric(1.5), None).with_priority(1)));
+    world.add(Sphere::new(&Point3::new(-1.4, 1.2, 0.0), 1.2, glass));
+    let juice = Material::interior(Material::dielectric(1.33), Medium::absorbing(Color::new(0.1, 0.8, 2.5)), 2);
+    world.add(Sphere::new(&Point3::new(-1.4, 1.2, 0.0), 1.1, juice));
+
+    // Air bubble floating in the juice
+    let bubble = Arc::new(Material::Interior(Interior::new(Material::dielectric(1.0), None).with_priority(3)));
+    world.add(Sphere::new(&Point3::new(-1.1, 1.5, 0.6), 0.3, bubble));
+
+    // Smoky glass block: a scattering medium behind a refracting surface
+    let smoke = Medium::new(Color::new(0.05, 0.05, 0.05), Color::new(0.8, 0.8, 0.8))
+        .with_phase_function(Material::henyey_greenstein(Color::new(1.0, 1.0, 1.0), 0.6));
+    let smoky_glass = Material::interior(Material::dielectric(1.5), smoke, 1);
+    world.add(Cuboid::new(&Point3::new(0.6, 0.0, -0.9), &Point3::new(2.4, 1.8, 0.9), smoky_glass));
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 5.0, -1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(6.0, 6.0, 6.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "interior_media".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.35, 0.4, 0.5));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.5, 8.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn subsurface_spheres() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.5, 0.5, 0.5));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), ground));
+
+    // Surface color and mean free path per channel: red light travels furthest in skin
+    let materials = [
+        Material::subsurface(Color::new(0.83, 0.79, 0.75), Color::new(0.4, 0.4, 0.4), 1.5),    // Marble
+        Material::subsurface(Color::new(0.85, 0.55, 0.45), Color::new(0.5, 0.25, 0.15), 1.4),  // Skin
+        Material::subsurface(Color::new(0.95, 0.93, 0.88), Color::new(0.3, 0.3, 0.25), 1.35),  // Milk
+        Material::subsurface(Color::new(0.9, 0.75, 0.4), Color::new(0.6, 0.45, 0.2), 1.45),    // Wax
+    ];
+    for (i, material) in materials.into_iter().enumerate() {
+        let x = -3.3 + 2.2 * i as f64;
+        world.add(Sphere::new(&Point3::new(x, 1.0, 0.0), 1.0, material));
+    }
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 5.0, -1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(6.0, 6.0, 6.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "subsurface_spheres".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 200;
+    cam.background = Background::solid(Color::new(0.2, 0.22, 0.25));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.5, 9.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn instancing() {
+    let mut world = HittableList::new();
+
+    let ground = Material::lambertian(Color::new(0.45, 0.45, 0.4));
+    world.add(Quad::new(&Point3::new(-20.0, 0.0, -20.0), &Vec3::new(40.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 40.0), ground));
+
+    // One octahedron crystal mesh shared by every instance
+    let positions = vec![
+        Point3::new(0.0, 1.0, 0.0), Point3::new(0.0, -1.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0), Point3::new(-1.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0), Point3::new(0.0, 0.0, -1.0),
+    ];
+    let indices = vec![
+        [0, 4, 2], [0, 3, 4], [0, 5, 3], [0, 2, 5],
+        [1, 2, 4], [1, 4, 3], [1, 3, 5], [1, 5, 2],
+    ];
+    let crystal = Arc::new(Hittable::from(TriangleMesh::new(positions, indices, Material::metal(Color::new(0.7, 0.6, 0.9), 0.2), false)));
+
+    // A field of crystals, each tilted about a random axis and stretched along its length
+    let mut field = HittableList::new();
+    for _ in 0..2000 {
+        let center = Point3::new(random_f64_range(-8.0, 8.0), 0.0, random_f64_range(-10.0, 2.0));
+        if (center - Point3::new(0.0, 0.0, 0.0)).length() < 1.6 {
+            continue;
+        }
+        let width = random_f64_range(0.05, 0.15);
+        let transform = Transform::scaling(Vec3::new(width, random_f64_range(0.2, 0.6), width))
+            .rotate(Vec3::random_unit_vector(), random_f64_range(0.0, 40.0))
+            .translate(center);
+        field.add(Hittable::instance(Arc::clone(&crystal), transform));
+    }
+    world.add(field.into_bvh());
+
+    // Non-uniform scale turns a sphere into an ellipsoid with correctly transformed normals
+    let ellipsoid = Sphere::new(&Point3::new(0.0, 0.0, 0.0), 1.0, Material::dielectric(1.5));
+    let transform = Transform::scaling(Vec3::new(1.2, 0.6, 0.8))
+        .rotate(Vec3::new(1.0, 0.0, 1.0), 30.0)
+        .translate(Vec3::new(0.0, 0.9, 0.0));
+    world.add(Hittable::instance(Hittable::from(ellipsoid), transform));
+
+    // Sheared box
+    let block = Cuboid::new(&Point3::new(-0.5, 0.0, -0.5), &Point3::new(0.5, 1.5, 0.5), Material::lambertian(Color::new(0.8, 0.3, 0.2)));
+    let transform = Transform::shearing(0.4, 0.0, 0.0, 0.0, 0.0, 0.3).translate(Vec3::new(2.5, 0.0, -1.0));
+    world.add(Hittable::instance(Hittable::from(block), transform));
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 6.0, -1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(8.0, 8.0, 8.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "instancing".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.5, 0.6, 0.75));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 3.0, 8.0);
+    cam.look_at = Point3::new(0.0, 0.6, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn motion_blur() {
+    let mut world = HittableList::new();
+
+    let checker = Texture::checker(0.5, Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), Material::lambertian_tex(checker)));
+
+    // Box sliding to the right while turning a quarter turn about its vertical axis
+    let block = Arc::new(Hittable::from(Cuboid::new(&Point3::new(-0.5, 0.0, -0.5), &Point3::new(0.5, 1.0, 0.5), Material::lambertian(Color::new(0.8, 0.3, 0.2)))));
+    let slide = AnimatedTransform::between(
+        Keyframe::new(0.0).with_translation(Vec3::new(-3.2, 0.0, 0.0)),
+        Keyframe::new(1.0).with_rotation(Vec3::new(0.0, 1.0, 0.0), 90.0).with_translation(Vec3::new(-2.2, 0.0, 0.0)),
+    );
+    world.add(Hittable::animated(block, slide));
+
+    // Propeller spinning a full turn, keyed every quarter turn so it does not take the short way back
+    let blade = Arc::new(Hittable::from(Cuboid::new(&Point3::new(-1.0, -0.08, -0.02), &Point3::new(1.0, 0.08, 0.02), Material::metal(Color::new(0.8, 0.8, 0.85), 0.1))));
+    let spin = AnimatedTransform::new((0..=4).map(|i| {
+        Keyframe::new(i as f64 / 4.0)
+            .with_rotation(Vec3::new(0.0, 0.0, 1.0), 90.0 * i as f64)
+            .with_translation(Vec3::new(0.0, 1.4, 0.0))
+    }).collect());
+    world.add(Hittable::animated(blade, spin));
+
+    // Sphere swelling and squashing as it bounces
+    let ball = Arc::new(Hittable::from(Sphere::new(&Point3::new(0.0, 0.0, 0.0), 0.5, Material::lambertian(Color::new(0.2, 0.4, 0.8)))));
+    let bounce = AnimatedTransform::new(vec![
+        Keyframe::new(0.0).with_scale(Vec3::new(1.3, 0.7, 1.3)).with_translation(Vec3::new(2.6, 0.35, 0.0)),
+        Keyframe::new(0.5).with_translation(Vec3::new(2.6, 1.2, 0.0)),
+        Keyframe::new(1.0).with_scale(Vec3::new(0.9, 1.2, 0.9)).with_translation(Vec3::new(2.6, 1.8, 0.0)),
+    ]);
+    world.add(Hittable::animated(ball, bounce));
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 5.0, -1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(6.0, 6.0, 6.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "motion_blur".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.6, 0.7, 0.9));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(0.0, 2.0, 8.0);
+    cam.look_at = Point3::new(0.0, 1.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    // Shutter open for the first half of the motion only
+    cam.shutter_open = 0.0;
+    cam.shutter_close = 0.5;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn deforming_mesh() {
+    let mut world = HittableList::new();
+
+    let checker = Texture::checker(0.5, Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), Material::lambertian_tex(checker)));
+    world.add(Cuboid::new(&Point3::new(-2.05, 0.0, -0.05), &Point3::new(-1.95, 3.0, 0.05), Material::metal(Color::new(0.7, 0.7, 0.7), 0.3)));
+
+    // Flag rippling in the wind: a grid whose vertices follow a travelling wave, keyed five times
+    // over the shutter so the blur follows the curved motion rather than a straight line.
+    let (columns, rows) = (40, 24);
+    let indices: Vec<[u32; 3]> = (0..rows).flat_map(|j| (0..columns).flat_map(move |i| {
+        let corner = |di: u32, dj: u32| (j + dj) * (columns + 1) + i + di;
+        [[corner(0, 0), corner(1, 0), corner(1, 1)], [corner(0, 0), corner(1, 1), corner(0, 1)]]
+    })).collect();
+    let keyframes: Vec<(f64, Vec<Point3>)> = (0..=4).map(|k| {
+        let time = k as f64 / 4.0;
+        let positions = (0..=rows).flat_map(|j| (0..=columns).map(move |i| {
+            let u = i as f64 / columns as f64;
+            let v = j as f64 / rows as f64;
+            let ripple = 0.5 * u * (std::f64::consts::TAU * (1.5 * u - 1.2 * time) + v).sin();
+            Point3::new(-1.95 + 3.0 * u, 1.2 + 1.7 * v - 0.15 * u * u, ripple)
+        })).collect();
+        (time, positions)
+    }).collect();
+    world.add(TriangleMesh::new_deforming(keyframes, indices, Material::lambertian(Color::new(0.8, 0.15, 0.1)), true));
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 5.0, 1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(6.0, 6.0, 6.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "deforming_mesh".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 200;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.6, 0.7, 0.9));
+
+    cam.v_fov = 40.0;
+    cam.look_from = Point3::new(4.5, 2.5, 5.5);
+    cam.look_at = Point3::new(0.0, 1.8, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn camera_flyby() {
+    let mut world = HittableList::new();
+
+    let checker = Texture::checker(0.5, Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), Material::lambertian_tex(checker)));
+    world.add(Sphere::new(&Point3::new(-1.5, 0.7, 0.0), 0.7, Material::dielectric(1.5)));
+    world.add(Sphere::new(&Point3::new(1.5, 0.7, 0.0), 0.7, Material::metal(Color::new(0.8, 0.6, 0.2), 0.05)));
+
+    // Ball bouncing once per second between the spheres, keyed in seconds of scene time
+    let ball = Arc::new(Hittable::from(Sphere::new(&Point3::new(0.0, 0.0, 0.0), 0.4, Material::lambertian(Color::new(0.2, 0.4, 0.8)))));
+    let bounce = AnimatedTransform::new((0..=8).map(|i| {
+        let height = if i % 2 == 0 { 0.4 } else { 2.0 };
+        Keyframe::new(i as f64 * 0.5).with_translation(Vec3::new(0.0, height, 0.0))
+    }).collect());
+    world.add(Hittable::animated(ball, bounce));
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 5.0, -1.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(6.0, 6.0, 6.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    // Camera circling halfway around the scene while zooming in, with a rack focus from the glass
+    // sphere to the metal one
+    let path = CameraPath::new(vec![
+        CameraKeyframe::new(0.0, Point3::new(-6.0, 2.0, 6.0), Point3::new(0.0, 0.8, 0.0), 40.0).with_focus_dist(7.5),
+        CameraKeyframe::new(2.0, Point3::new(0.0, 3.0, 8.5), Point3::new(0.0, 0.8, 0.0), 35.0),
+        CameraKeyframe::new(4.0, Point3::new(6.0, 2.0, 6.0), Point3::new(0.0, 0.8, 0.0), 30.0).with_focus_dist(7.5),
+    ]);
+    let sequence = Sequence::new(96, 24.0)
+        .with_output_dir("camera_flyby")
+        .with_camera_path(path);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "camera_flyby".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 640;
+    cam.samples_per_pixel = 100;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.6, 0.7, 0.9));
+
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 1.0;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render_sequence(world.into_bvh(), Some(sampling_target), &sequence);
+}
+
+fn projections() {
+    let mut world = HittableList::new();
+
+    let checker = Texture::checker(0.5, Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Quad::new(&Point3::new(-20.0, 0.0, -20.0), &Vec3::new(40.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 40.0), Material::lambertian_tex(checker)));
+
+    // Ring of spheres and boxes all around the camera, so every direction shows something
+    for k in 0..12 {
+        let angle = k as f64 * std::f64::consts::TAU / 12.0;
+        let position = Point3::new(4.0 * angle.sin(), 0.0, -4.0 * angle.cos());
+        let color = Color::new(0.5 + 0.4 * angle.cos(), 0.5 + 0.4 * (angle + 2.0).cos(), 0.5 + 0.4 * (angle + 4.0).cos());
+        if k % 2 == 0 {
+            world.add(Sphere::new(&(position + Vec3::new(0.0, 0.6, 0.0)), 0.6, Material::lambertian(color)));
+        } else {
+            let block = Cuboid::new(&Point3::new(-0.4, 0.0, -0.4), &Point3::new(0.4, 1.6, 0.4), Material::metal(color, 0.2));
+            world.add(Hittable::instance(Hittable::from(block), Transform::rotation(Vec3::new(0.0, 1.0, 0.0), -angle.to_degrees()).translate(position)));
+        }
+    }
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-1.5, 6.0, -1.5), &Vec3::new(3.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 3.0), Material::diffuse_light(Color::new(8.0, 8.0, 8.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let world = world.into_bvh();
+    let sampling_target = Arc::new(Hittable::from(lights));
+
+    let mut cam = Camera::default();
+    cam.image_width = 800;
+    cam.samples_per_pixel = 100;
+    cam.max_depth = 50;
+    cam.background = Background::gradient(Color::new(1.0, 1.0, 1.0), Color::new(0.5, 0.7, 1.0));
+
+    cam.look_from = Point3::new(0.0, 1.0, 0.0);
+    cam.look_at = Point3::new(0.0, 1.0, -1.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.aperture_angle = 0.0;
+
+    // Plan view from above, without perspective
+    cam.scene_name = "projection_orthographic".to_string();
+    cam.aspect_ratio = 1.0;
+    cam.projection = Projection::orthographic(11.0);
+    cam.look_from = Point3::new(0.0, 5.0, 0.01);
+    cam.look_at = Point3::new(0.0, 0.0, 0.0);
+    cam.render(world.clone(), Some(sampling_target.clone()));
+
+    // Fisheye looking straight up, seeing the whole ring at the rim
+    cam.scene_name = "projection_fisheye".to_string();
+    cam.projection = Projection::fisheye_equisolid(200.0);
+    cam.look_from = Point3::new(0.0, 0.3, 0.0);
+    cam.look_at = Point3::new(0.0, 1.3, 0.0);
+    cam.v_up = Vec3::new(0.0, 0.0, -1.0);
+    cam.render(world.clone(), Some(sampling_target.clone()));
+
+    // Full panorama, then the same in over-under stereo for a VR headset
+    cam.scene_name = "projection_equirectangular".to_string();
+    cam.aspect_ratio = 2.0;
+    cam.projection = Projection::Equirectangular;
+    cam.look_from = Point3::new(0.0, 1.0, 0.0);
+    cam.look_at = Point3::new(0.0, 1.0, -1.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.render(world.clone(), Some(sampling_target.clone()));
+
+    cam.scene_name = "projection_equirectangular_stereo".to_string();
+    cam.stereo = Some(Stereo::top_bottom(0.064));
+    cam.render(world, Some(sampling_target));
+}
+
+fn lens_camera() {
+    let mut world = HittableList::new();
+
+    // Scene modeled in meters, matching the lens prescription in millimeters
+    let checker = Texture::checker(0.1, Color::new(0.2, 0.3, 0.1), Color::new(0.9, 0.9, 0.9));
+    world.add(Quad::new(&Point3::new(-10.0, 0.0, -20.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 22.0), Material::lambertian_tex(checker)));
+
+    // Row of spheres receding from the camera, the second one in focus
+    for k in 0..6 {
+        let distance = 1.0 + 0.75 * k as f64;
+        let color = Color::new(0.8 - 0.12 * k as f64, 0.3, 0.2 + 0.12 * k as f64);
+        world.add(Sphere::new(&Point3::new(0.25 - 0.12 * k as f64, 0.12, -distance), 0.12, Material::lambertian(color)));
+    }
+
+    // Small bright lights far behind, blurred into bokeh
+    for k in 0..15 {
+        let x = -3.0 + 0.45 * k as f64;
+        let y = 0.6 + 0.4 * (1.7 * k as f64).sin().abs();
+        world.add(Sphere::new(&Point3::new(x, y, -14.0 + (k % 3) as f64), 0.03, Material::diffuse_light(Color::new(40.0, 30.0, 15.0))));
+    }
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-2.0, 4.0, -4.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(4.0, 4.0, 4.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "lens_camera".to_string();
+
+    cam.aspect_ratio = 3.0 / 2.0;
+    cam.image_width = 900;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.05, 0.05, 0.08));
+
+    // 50mm f/2 prime on full frame, wide open; the field of view follows from the lens
+    cam.lens = Some(Arc::new(LensSystem::double_gauss_50mm()));
+    cam.look_from = Point3::new(0.0, 0.25, 0.0);
+    cam.look_at = Point3::new(-0.1, 0.15, -2.5);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.focus_dist = 1.75;
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
+}
+
+fn bokeh_tilt_shift() {
+    // ----- Shaped bokeh: a field of small lights far out of focus behind a sphere -----
+    let mut world = HittableList::new();
+    world.add(Sphere::new(&Point3::new(0.0, 0.0, 0.0), 0.5, Material::lambertian(Color::new(0.7, 0.2, 0.2))));
+    for k in 0..80 {
+        let x = -11.0 + 22.0 * random_f64();
+        let y = -6.0 + 12.0 * random_f64();
+        let color = Color::new(0.6 + 0.4 * random_f64(), 0.5 + 0.3 * random_f64(), 0.2 + 0.3 * random_f64());
+        world.add(Sphere::new(&Point3::new(x, y, -20.0 - (k % 5) as f64), 0.15, Material::diffuse_light(8.0 * color)));
+    }
+
+    let mut lights = HittableList::new();
+    let light = Quad::new(&Point3::new(-1.0, 3.0, 1.0), &Vec3::new(2.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 2.0), Material::diffuse_light(Color::new(4.0, 4.0, 4.0)));
+    world.add(light.clone());
+    lights.add(light);
+
+    let mut cam = Camera::default();
+    cam.scene_name = "bokeh_blades".to_string();
+
+    cam.aspect_ratio = 16.0 / 9.0;
+    cam.image_width = 800;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.01, 0.01, 0.02));
+
+    cam.v_fov = 30.0;
+    cam.look_from = Point3::new(0.0, 0.0, 4.0);
+    cam.look_at = Point3::new(0.0, 0.0, 0.0);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+
+    // Six straight blades, with the barrel squeezing highlights into cat's eyes toward the corners
+    cam.aperture_angle = 4.5;
+    cam.focus_dist = 4.0;
+    cam.aperture = Aperture::polygon(6, 15.0);
+    cam.cat_eye = 0.8;
+
+    cam.render(world.into_bvh(), Some(Arc::new(Hittable::from(lights))));
+
+    // ----- Tilt-shift: a town seen from above, with the focal plane tilted into a thin band -----
+    let mut world = HittableList::new();
+    world.add(Quad::new(&Point3::new(-30.0, 0.0, -30.0), &Vec3::new(60.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 60.0), Material::lambertian(Color::new(0.35, 0.5, 0.3))));
+    for row in -8..8 {
+        for column in -6..6 {
+            if random_f64() < 0.3 { continue; }
+            let height = 0.5 + 1.5 * random_f64();
+            let color = Color::new(0.5 + 0.5 * random_f64(), 0.5 + 0.4 * random_f64(), 0.4 + 0.4 * random_f64());
+            let corner = Point3::new(column as f64 * 2.0 + 0.3, 0.0, row as f64 * 2.0 + 0.3);
+            world.add(Cuboid::new(&corner, &(corner + Vec3::new(1.4, height, 1.4)), Material::lambertian(color)));
+        }
+    }
+
+    let mut lights = HittableList::new();
+    let sun = Quad::new(&Point3::new(-10.0, 30.0, -10.0), &Vec3::new(20.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 20.0), Material::diffuse_light(Color::new(3.0, 3.0, 2.7)));
+    world.add(sun.clone());
+    lights.add(sun);
+
+    cam.scene_name = "tilt_shift".to_string();
+    cam.background = Background::solid(Color::new(0.6, 0.7, 0.9));
+    cam.look_from = Point3::new(0.0, 14.0, 18.0);
+    cam.look_at = Point3::new(0.0, 0.0, 0.0);
+    cam.focus_dist = 22.8;
+    cam.aperture_angle = 5.0;
+    cam.aperture = Aperture::Circular;
+    cam.cat_eye = 0.0;
+
+    // Tilting the focal plane against the ground leaves only a narrow strip sharp, as on a miniature,
+    // and shifting the lens down frames the town without tipping the camera further
+    cam.tilt = (-25.0, 0.0);
+    cam.shift = (0.0, -0.1);
+
+    cam.render(world.into_bvh(), Some(Arc::new(Hittable::from(lights))));
+}
+
+fn physical_exposure() {
+    let mut world = HittableList::new();
+
+    // A 4m x 3m room in meters, lit by a 1600 lumen ceiling panel
+    let white = Material::lambertian(Color::new(0.73, 0.73, 0.73));
+    world.add(Quad::new(&Point3::new(-2.0, 0.0, -3.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 6.0), Material::lambertian(Color::new(0.45, 0.3, 0.2))));
+    world.add(Quad::new(&Point3::new(-2.0, 2.6, -3.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 6.0), white.clone()));
+    world.add(Quad::new(&Point3::new(-2.0, 0.0, -3.0), &Vec3::new(4.0, 0.0, 0.0), &Vec3::new(0.0, 2.6, 0.0), white.clone()));
+    world.add(Quad::new(&Point3::new(-2.0, 0.0, -3.0), &Vec3::new(0.0, 0.0, 6.0), &Vec3::new(0.0, 2.6, 0.0), Material::lambertian(Color::new(0.6, 0.2, 0.2))));
+    world.add(Quad::new(&Point3::new(2.0, 0.0, -3.0), &Vec3::new(0.0, 0.0, 6.0), &Vec3::new(0.0, 2.6, 0.0), Material::lambertian(Color::new(0.2, 0.3, 0.6))));
+
+    let mut lights = HittableList::new();
+    let panel = Quad::new(&Point3::new(-0.4, 2.59, -1.4), &Vec3::new(0.8, 0.0, 0.0), &Vec3::new(0.0, 0.0, 0.8), white.clone())
+        .with_emitter(DiffuseLight::blackbody(3200.0), LightPower::Lumens(1600.0));
+    world.add(panel.clone());
+    lights.add(panel);
+
+    world.add(Cuboid::new(&Point3::new(-0.6, 0.0, -2.0), &Point3::new(0.6, 0.75, -1.4), Material::lambertian(Color::new(0.5, 0.4, 0.3))));
+    world.add(Sphere::new(&Point3::new(-0.25, 0.87, -1.7), 0.12, Material::metal(Color::new(0.9, 0.9, 0.9), 0.05)));
+    world.add(Sphere::new(&Point3::new(0.3, 0.85, -1.6), 0.1, Material::dielectric(1.5)));
+
+    // Ball rolling across the floor at 2 m/s, blurred over the shutter time
+    let ball = Arc::new(Hittable::from(Sphere::new(&Point3::new(0.0, 0.1, 0.0), 0.1, Material::lambertian(Color::new(0.9, 0.7, 0.1)))));
+    world.add(Hittable::animated(ball, AnimatedTransform::between(
+        Keyframe::new(0.0).with_translation(Vec3::new(-0.8, 0.0, -0.6)),
+        Keyframe::new(1.0).with_translation(Vec3::new(1.2, 0.0, -0.6)),
+    )));
+
+    let mut cam = Camera::default();
+    cam.scene_name = "physical_exposure".to_string();
+
+    cam.aspect_ratio = 3.0 / 2.0;
+    cam.image_width = 900;
+    cam.samples_per_pixel = 400;
+    cam.max_depth = 50;
+    cam.background = Background::solid(Color::new(0.0, 0.0, 0.0));
+
+    cam.look_from = Point3::new(0.0, 1.2, 2.5);
+    cam.look_at = Point3::new(0.0, 0.8, -1.7);
+    cam.v_up = Vec3::new(0.0, 1.0, 0.0);
+    cam.focus_dist = 4.25;
+
+    // Handheld indoor settings: 35mm at f/2.8, 1/30s, ISO 1600
+    cam.physical = Some(PhysicalCamera::new(35.0, 2.8, 1.0 / 30.0, 1600.0));
+
+    let sampling_target = Arc::new(Hittable::from(lights));
+    cam.render(world.into_bvh(), Some(sampling_target));
 }
 
 fn main() {
@@ -644,6 +1788,30 @@ fn main() {
         10 => pyramid(),
         11 => ply_model_scene(),
         12 => ply_model_spin(),
+        13 => principled_spheres(),
+        14 => coated_spheres(),
+        15 => rough_diffuse(),
+        16 => emission_modes(),
+        17 => blackbody_lights(),
+        18 => delta_lights(),
+        19 => environment_lighting(),
+        20 => sky_scene(),
+        21 => backplate(),
+        22 => textured_emitters(),
+        23 => volume_clouds(),
+        24 => volume_file(),
+        25 => anisotropic_media(),
+        26 => chromatic_media(),
+        27 => interior_media(),
+        28 => subsurface_spheres(),
+        29 => instancing(),
+        30 => motion_blur(),
+        31 => deforming_mesh(),
+        32 => camera_flyby(),
+        33 => projections(),
+        34 => lens_camera(),
+        35 => bokeh_tilt_shift(),
+        36 => physical_exposure(),
         _ => println!("No scene selected."),
     }
 }
@@ -2,7 +2,12 @@ use std::sync::Arc;
 use std::f64::consts::PI;
 
 use crate::hittable::HitRecord;
+use crate::medium::Medium;
+use crate::microfacet;
+use crate::onb::ONB;
 use crate::pdf::PDF;
+use crate::phase;
+use crate::photometry::{self, LightPower};
 use crate::prelude::*;
 use crate::texture::{Texture, SolidColor};
 
@@ -38,6 +43,15 @@ pub enum Material {
     Dielectric(Dielectric),
     DiffuseLight(DiffuseLight),
     Isotropic(Isotropic),
+    HenyeyGreenstein(HenyeyGreenstein),
+    Principled(Principled),
+    Coated(Coated),
+    OrenNayar(OrenNayar),
+    Translucent(Translucent),
+    Tinted(Tinted),
+    Passthrough(Passthrough),
+    Interior(Interior),
+    Subsurface(Subsurface),
     // Etc.
 }
 
@@ -51,6 +65,15 @@ impl Material {
             Material::Dielectric(mat) => mat.scatter(ray_in, rec, srec),
             Material::DiffuseLight(_) => false, // DiffuseLight does not scatter
             Material::Isotropic(mat) => mat.scatter(ray_in, rec, srec),
+            Material::HenyeyGreenstein(mat) => mat.scatter(ray_in, rec, srec),
+            Material::Principled(mat) => mat.scatter(ray_in, rec, srec),
+            Material::Coated(mat) => mat.scatter(ray_in, rec, srec),
+            Material::OrenNayar(mat) => mat.scatter(ray_in, rec, srec),
+            Material::Translucent(mat) => mat.scatter(ray_in, rec, srec),
+            Material::Tinted(mat) => mat.scatter(ray_in, rec, srec),
+            Material::Passthrough(mat) => mat.scatter(ray_in, rec, srec),
+            Material::Interior(mat) => mat.surface.scatter(ray_in, rec, srec),
+            Material::Subsurface(mat) => mat.scatter(ray_in, rec, srec),
             // Etc.
         }
     }
@@ -60,10 +83,23 @@ impl Material {
     pub fn emitted(&self, ray_in: &Ray, rec: &HitRecord, u: f64, v: f64, point: &Point3) -> Color {
         match self {
             Material::DiffuseLight(mat) => mat.emitted(ray_in, rec, u, v, point),
+            Material::Coated(mat) => mat.base.emitted(ray_in, rec, u, v, point),
+            Material::Interior(mat) => mat.surface.emitted(ray_in, rec, u, v, point),
             _ => Color::zero(), // Non-emissive materials emit no light
         }
     }
 
+    /// The emission texture of a textured emitter, used to light-sample bright regions more often.
+    /// Returns None for non-emissive materials and constant-color emitters.
+    pub fn emission_texture(&self) -> Option<&Arc<Texture>> {
+        match self {
+            Material::DiffuseLight(mat) => mat.textured_emission(),
+            Material::Coated(mat) => mat.base.emission_texture(),
+            Material::Interior(mat) => mat.surface.emission_texture(),
+            _ => None,
+        }
+    }
+
     /// Implementation of scattering_pdf method for Material enum.
     /// "What does the material physically say the scattering distribution should be in that direction?”
     #[inline]
@@ -71,10 +107,57 @@ impl Material {
         match self {
             Material::Lambertian(mat) => { mat.scattering_pdf(ray_in, rec, scattered) },
             Material::Isotropic(mat) => { mat.scattering_pdf(ray_in, rec, scattered) },
+            Material::HenyeyGreenstein(mat) => { mat.scattering_pdf(ray_in, rec, scattered) },
+            Material::OrenNayar(mat) => { mat.scattering_pdf(ray_in, rec, scattered) },
+            Material::Translucent(mat) => { mat.scattering_pdf(ray_in, rec, scattered) },
+            Material::Tinted(mat) => { mat.base.scattering_pdf(ray_in, rec, scattered) },
+            Material::Interior(mat) => { mat.surface.scattering_pdf(ray_in, rec, scattered) },
+            Material::Subsurface(mat) => { mat.scattering_pdf(ray_in, rec, scattered) },
             _ => 0.0, // Default PDF for non-Lambertian materials
         }
     }
 
+    /// Implementation of scattering_bsdf method for Material enum.
+    /// Returns the colored response (BSDF times cosine) for light arriving along `scattered`.
+    /// Materials with a scalar lobe fall back to `attenuation * scattering_pdf`.
+    #[inline]
+    pub fn scattering_bsdf(&self, ray_in: &Ray, rec: &HitRecord, srec: &ScatterRecord, scattered: &Ray) -> Color {
+        match self {
+            Material::Principled(mat) => mat.bsdf(ray_in, rec, scattered),
+            Material::Coated(mat) => mat.bsdf(ray_in, rec, srec, scattered),
+            Material::Tinted(mat) => mat.bsdf(ray_in, rec, srec, scattered),
+            Material::Interior(mat) => mat.surface.scattering_bsdf(ray_in, rec, srec, scattered),
+            _ => srec.attenuation * self.scattering_pdf(ray_in, rec, scattered),
+        }
+    }
+
+    /// The volume this surface encloses, for materials attached to closed surfaces with `Interior`.
+    pub fn as_interior(&self) -> Option<&Interior> {
+        match self {
+            Material::Interior(mat) => Some(mat),
+            _ => None,
+        }
+    }
+
+    /// Tint of light passing straight through the surface, or None if it scatters or blocks light.
+    pub fn passthrough_tint(&self) -> Option<Color> {
+        match self {
+            Material::Passthrough(mat) => Some(mat.tint),
+            Material::Interior(mat) => mat.surface.passthrough_tint(),
+            _ => None,
+        }
+    }
+
+    /// Refractive index of the material at the hit point, or None if it does not refract.
+    pub fn refraction_index(&self, rec: &HitRecord) -> Option<f64> {
+        match self {
+            Material::Dielectric(mat) => Some(mat.refraction_index.scalar_value(rec.u, rec.v, &rec.point)),
+            Material::Interior(mat) => mat.surface.refraction_index(rec),
+            Material::Subsurface(mat) => Some(mat.refraction_index),
+            _ => None,
+        }
+    }
+
     // Convenience Arc constructors
 
     /// Create an Arc<Material> lambertian from a Color.
@@ -85,14 +168,34 @@ impl Material {
     pub fn lambertian_tex(tex: Arc<Texture>) -> Arc<Material> {
         Arc::new(Material::Lambertian(Lambertian::from_texture(tex)))
     }
+    /// Create an Arc<Material> Oren-Nayar rough diffuse from a Color and roughness (facet slope deviation in degrees).
+    pub fn oren_nayar(albedo: Color, sigma_deg: f64) -> Arc<Material> {
+        Arc::new(Material::OrenNayar(OrenNayar::new(albedo, sigma_deg)))
+    }
+    /// Create an Arc<Material> Oren-Nayar rough diffuse from a Texture and roughness in degrees.
+    pub fn oren_nayar_tex(tex: Arc<Texture>, sigma_deg: f64) -> Arc<Material> {
+        Arc::new(Material::OrenNayar(OrenNayar::from_texture(tex, sigma_deg)))
+    }
+    /// Create an Arc<Material> translucent diffuse from a Color and the fraction of light transmitted.
+    pub fn translucent(albedo: Color, transmission: f64) -> Arc<Material> {
+        Arc::new(Material::Translucent(Translucent::new(albedo, transmission)))
+    }
     /// Create an Arc<Material> metal from albedo Color and fuzz factor.
     pub fn metal(albedo: Color, fuzz: f64) -> Arc<Material> {
         Arc::new(Material::Metal(Metal::new(albedo, fuzz)))
     }
+    /// Create an Arc<Material> metal from albedo and fuzz Textures.
+    pub fn metal_tex(albedo: Arc<Texture>, fuzz: Arc<Texture>) -> Arc<Material> {
+        Arc::new(Material::Metal(Metal::from_textures(albedo, fuzz)))
+    }
     /// Create an Arc<Material> dielectric from refraction index.
     pub fn dielectric(refraction_index: f64) -> Arc<Material> {
         Arc::new(Material::Dielectric(Dielectric::new(refraction_index)))
     }
+    /// Create an Arc<Material> dielectric from refraction index and tint Textures.
+    pub fn dielectric_tex(refraction_index: Arc<Texture>, tint: Arc<Texture>) -> Arc<Material> {
+        Arc::new(Material::Dielectric(Dielectric::from_textures(refraction_index, tint)))
+    }
     /// Create an Arc<Material> diffuse light from emit Color.
     pub fn diffuse_light(emit_color: Color) -> Arc<Material> {
         Arc::new(Material::DiffuseLight(DiffuseLight::new(emit_color)))
@@ -101,6 +204,10 @@ impl Material {
     pub fn diffuse_light_tex(tex: Arc<Texture>) -> Arc<Material> {
         Arc::new(Material::DiffuseLight(DiffuseLight::from_texture(tex)))
     }
+    /// Create an Arc<Material> coated material: a smooth dielectric coat of the given IOR over a base material.
+    pub fn coated(base: Arc<Material>, ior: f64) -> Arc<Material> {
+        Arc::new(Material::Coated(Coated::new(base, ior)))
+    }
     /// Create an Arc<Material> isotropic from a Color.
     pub fn isotropic(albedo: Color) -> Arc<Material> {
         Arc::new(Material::Isotropic(Isotropic::new(albedo)))
@@ -109,6 +216,36 @@ impl Material {
     pub fn isotropic_tex(tex: Arc<Texture>) -> Arc<Material> {
         Arc::new(Material::Isotropic(Isotropic::from_texture(tex)))
     }
+    /// Create an Arc<Material> Henyey-Greenstein phase function from a Color and asymmetry `g`.
+    pub fn henyey_greenstein(albedo: Color, g: f64) -> Arc<Material> {
+        Arc::new(Material::HenyeyGreenstein(HenyeyGreenstein::new(albedo, g)))
+    }
+    /// Create an Arc<Material> double Henyey-Greenstein phase function: a `weight` blend of a lobe
+    /// with asymmetry `g1` and one with `g2`, typically forward and backward.
+    pub fn double_henyey_greenstein(albedo: Color, g1: f64, g2: f64, weight: f64) -> Arc<Material> {
+        Arc::new(Material::HenyeyGreenstein(HenyeyGreenstein::double(albedo, g1, g2, weight)))
+    }
+    /// Create an Arc<Material> that scales the light scattered by a base material per color channel.
+    pub fn tinted(base: Arc<Material>, tint: Color) -> Arc<Material> {
+        Arc::new(Material::Tinted(Tinted::new(base, tint)))
+    }
+    /// Create an Arc<Material> invisible surface that rays continue straight through, tinted by a Color.
+    pub fn passthrough(tint: Color) -> Arc<Material> {
+        Arc::new(Material::Passthrough(Passthrough::new(tint)))
+    }
+    /// Create an Arc<Material> closed surface filled with a medium, e.g. glass holding a liquid.
+    /// Where volumes overlap, the one with the higher priority wins.
+    pub fn interior(surface: Arc<Material>, medium: Medium, priority: u32) -> Arc<Material> {
+        Arc::new(Material::Interior(Interior::new(surface, Some(medium)).with_priority(priority)))
+    }
+    /// Create an Arc<Material> subsurface scattering material, for skin, marble, wax or milk: a glossy
+    /// surface of the given IOR over a random walk through a scattering medium, with the color seen
+    /// from outside and the mean free path per channel. Apply it to closed meshes, and raise
+    /// `max_depth` for bright materials since every step of the walk counts as a bounce.
+    pub fn subsurface(albedo: Color, mean_free_path: Color, ior: f64) -> Arc<Material> {
+        let surface = Arc::new(Material::Subsurface(Subsurface::new(ior)));
+        Self::interior(surface, Medium::subsurface(albedo, mean_free_path), 0)
+    }
 }
 
 // Default material (Lambertian gray)
@@ -131,7 +268,7 @@ macro_rules! impl_material_from {
         )+
     };
 }
-impl_material_from!(Lambertian, Metal, Dielectric, DiffuseLight);
+impl_material_from!(Lambertian, Metal, Dielectric, DiffuseLight, Isotropic, HenyeyGreenstein, Principled, Coated, OrenNayar, Translucent, Tinted, Passthrough, Interior, Subsurface);
 
 // From material type to Arc<Material>
 macro_rules! impl_arc_material_from {
@@ -145,7 +282,7 @@ macro_rules! impl_arc_material_from {
         )+
     };
 }
-impl_arc_material_from!(Lambertian, Metal, Dielectric, DiffuseLight);
+impl_arc_material_from!(Lambertian, Metal, Dielectric, DiffuseLight, Isotropic, HenyeyGreenstein, Principled, Coated, OrenNayar, Translucent, Tinted, Passthrough, Interior, Subsurface);
 
 // ----- Lambertian (diffuse) Material -----
 
@@ -158,14 +295,14 @@ pub struct Lambertian {
 impl Lambertian {
     /// Constructor from a Color.
     pub fn new(albedo: Color) -> Self {
-        Self { 
+        Self {
             tex: Arc::new(Texture::from(SolidColor::new(albedo))),
         }
     }
 
     /// Constructor from a Texture reference counter.
     pub fn from_texture(tex: Arc<Texture>) -> Self {
-        Self { 
+        Self {
             tex,
         }
     }
@@ -187,32 +324,146 @@ impl Lambertian {
     }
 }
 
+// ----- Oren-Nayar (rough diffuse) Material -----
+
+/// A rough diffuse material using the qualitative Oren-Nayar model, for clay, concrete or the moon.
+/// Roughness is the standard deviation of the microfacet slope angle; zero reduces to Lambertian.
+#[derive(Clone)]
+pub struct OrenNayar {
+    tex: Arc<Texture>,
+    a: f64, // Precomputed A term: 1 - 0.5 * sigma^2 / (sigma^2 + 0.33)
+    b: f64, // Precomputed B term: 0.45 * sigma^2 / (sigma^2 + 0.09)
+}
+
+impl OrenNayar {
+    /// Constructor from a Color and roughness in degrees.
+    pub fn new(albedo: Color, sigma_deg: f64) -> Self {
+        Self::from_texture(Texture::solid(albedo), sigma_deg)
+    }
+
+    /// Constructor from a Texture reference counter and roughness in degrees.
+    pub fn from_texture(tex: Arc<Texture>, sigma_deg: f64) -> Self {
+        let sigma = degrees_to_radians(sigma_deg.max(0.0));
+        let sigma2 = sigma * sigma;
+        Self {
+            tex,
+            a: 1.0 - 0.5 * sigma2 / (sigma2 + 0.33),
+            b: 0.45 * sigma2 / (sigma2 + 0.09),
+        }
+    }
+
+    /// Scatter method for an Oren-Nayar material. Sampling is cosine-weighted like Lambertian.
+    #[inline]
+    fn scatter(&self, _ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        srec.attenuation = self.tex.value(rec.u, rec.v, &rec.point);
+        srec.pdf_ptr = Some(PDF::cosine(&rec.normal));
+        srec.skip_pdf = false;
+        true
+    }
+
+    /// Scattering PDF for an Oren-Nayar material: the Lambertian cosine lobe scaled by
+    /// `A + B * max(0, cos(phi_i - phi_o)) * sin(alpha) * tan(beta)`.
+    #[inline]
+    fn scattering_pdf(&self, ray_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let wi = Vec3::unit_vector(&scattered.direction);
+        let wo = -Vec3::unit_vector(&ray_in.direction);
+        let cos_i = Vec3::dot(&rec.normal, &wi);
+        if cos_i <= 0.0 {
+            return 0.0;
+        }
+        let cos_o = Vec3::dot(&rec.normal, &wo).clamp(EPSILON, 1.0);
+
+        let sin_i = (1.0 - cos_i * cos_i).max(0.0).sqrt();
+        let sin_o = (1.0 - cos_o * cos_o).max(0.0).sqrt();
+
+        // Cosine of the azimuthal difference, from the directions projected onto the tangent plane
+        let cos_phi = if sin_i > EPSILON && sin_o > EPSILON {
+            let proj_i = wi - cos_i * rec.normal;
+            let proj_o = wo - cos_o * rec.normal;
+            (Vec3::dot(&proj_i, &proj_o) / (sin_i * sin_o)).max(0.0)
+        } else {
+            0.0
+        };
+
+        // alpha = max(theta_i, theta_o), beta = min(theta_i, theta_o)
+        let (sin_alpha, tan_beta) = if cos_i > cos_o { (sin_o, sin_i / cos_i) } else { (sin_i, sin_o / cos_o) };
+
+        cos_i / PI * (self.a + self.b * cos_phi * sin_alpha * tan_beta)
+    }
+}
+
+// ----- Translucent (diffuse transmission) Material -----
+
+/// A thin translucent material that scatters diffusely on both sides, for leaves, paper or lamp shades.
+/// `transmission` is the fraction of scattered light leaving through the back side.
+#[derive(Clone)]
+pub struct Translucent {
+    tex: Arc<Texture>,
+    transmission: Arc<Texture>,
+}
+
+impl Translucent {
+    /// Constructor from a Color and the transmitted fraction in [0,1].
+    pub fn new(albedo: Color, transmission: f64) -> Self {
+        Self::from_textures(Texture::solid(albedo), Texture::scalar(transmission))
+    }
+
+    /// Constructor from albedo and transmitted fraction Textures.
+    pub fn from_textures(tex: Arc<Texture>, transmission: Arc<Texture>) -> Self {
+        Self { tex, transmission }
+    }
+
+    /// Scatter method for a Translucent material. Samples a cosine lobe on either side with equal probability.
+    #[inline]
+    fn scatter(&self, _ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        srec.attenuation = self.tex.value(rec.u, rec.v, &rec.point);
+        srec.pdf_ptr = Some(PDF::mixture(PDF::cosine(&rec.normal), PDF::cosine(&-rec.normal)));
+        srec.skip_pdf = false;
+        true
+    }
+
+    /// Scattering PDF for a Translucent material: a reflected cosine lobe weighted by `1 - transmission`
+    /// and a transmitted cosine lobe weighted by `transmission`.
+    #[inline]
+    fn scattering_pdf(&self, _ray_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let transmission = self.transmission.scalar_value(rec.u, rec.v, &rec.point).clamp(0.0, 1.0);
+        let cos_theta = Vec3::dot(&rec.normal, &Vec3::unit_vector(&scattered.direction));
+        if cos_theta >= 0.0 {
+            (1.0 - transmission) * cos_theta / PI
+        } else {
+            transmission * -cos_theta / PI
+        }
+    }
+}
+
 // ----- Metal Material -----
-// TODO: Add textures to Metal material
 
-/// A Metal material defined by an albedo color and fuzziness.
+/// A Metal material defined by an albedo texture and a fuzziness (roughness) texture.
 #[derive(Clone)]
 pub struct Metal {
-    albedo: Color,
-    fuzz: f64,
+    albedo: Arc<Texture>,
+    fuzz: Arc<Texture>, // Sampled as a scalar and clamped to [0,1]
 }
 
 impl Metal {
-    /// Constructor for Metal material.
+    /// Constructor for Metal material from an albedo Color and fuzz factor.
     pub fn new(albedo: Color, fuzz: f64) -> Self {
-        Self { 
-            albedo,
-            fuzz: if fuzz < 1.0 { fuzz } else { 1.0 },
-        }
+        Self::from_textures(Texture::solid(albedo), Texture::scalar(fuzz))
+    }
+
+    /// Constructor from albedo and fuzz Textures, e.g. for scratched metal or roughness maps.
+    pub fn from_textures(albedo: Arc<Texture>, fuzz: Arc<Texture>) -> Self {
+        Self { albedo, fuzz }
     }
 
     /// Scatter method for a Metal material.
     #[inline]
     fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let fuzz = self.fuzz.scalar_value(rec.u, rec.v, &rec.point).clamp(0.0, 1.0);
         let mut reflected = Vec3::reflect(&ray_in.direction, &rec.normal);
-        reflected = Vec3::unit_vector(&reflected) + (self.fuzz * Vec3::random_unit_vector());
+        reflected = Vec3::unit_vector(&reflected) + (fuzz * Vec3::random_unit_vector());
 
-        srec.attenuation = self.albedo;
+        srec.attenuation = self.albedo.value(rec.u, rec.v, &rec.point);
         srec.pdf_ptr = None;
         srec.skip_pdf = true;
         srec.skip_pdf_ray = Ray::new_with_time(rec.point, reflected, ray_in.time);
@@ -222,20 +473,25 @@ impl Metal {
 }
 
 // ----- Dielectric (glass-like) Material -----
-// TODO: Add textures to Dielectric material
 
-/// A Dielectric material defined by its refractive index.
+/// A Dielectric material defined by its refractive index and a transmission tint.
 #[derive(Clone)]
 pub struct Dielectric {
     // Refractive index in vacuum or air, or the ratio of the material's refractive index over
-    // the refractive index of the enclosing media
-    refraction_index: f64, // Index of Refraction
+    // the refractive index of the enclosing media. Sampled as a scalar.
+    refraction_index: Arc<Texture>, // Index of Refraction
+    tint: Arc<Texture>,             // Color applied to refracted rays
 }
 
 impl Dielectric {
-    /// Constructor for a Dielectric material.
+    /// Constructor for a clear Dielectric material.
     pub fn new(refraction_index: f64) -> Self {
-        Self { refraction_index }
+        Self::from_textures(Texture::scalar(refraction_index), Texture::solid(Color::new(1.0, 1.0, 1.0)))
+    }
+
+    /// Constructor from refractive index and tint Textures.
+    pub fn from_textures(refraction_index: Arc<Texture>, tint: Arc<Texture>) -> Self {
+        Self { refraction_index, tint }
     }
 
     /// A reflectance function using Schlick's approximation.
@@ -248,11 +504,15 @@ impl Dielectric {
     /// Scatter method for a Dielectric material.
     #[inline]
     fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
-        srec.attenuation = Color::new(1.0, 1.0, 1.0);
         srec.pdf_ptr = None;
         srec.skip_pdf = true;
 
-        let ri: f64 = if rec.front_face { 1.0 / self.refraction_index } else { self.refraction_index };
+        let refraction_index = self.refraction_index.scalar_value(rec.u, rec.v, &rec.point);
+        let ri: f64 = if rec.front_face {
+            rec.outside_ior / refraction_index
+        } else {
+            refraction_index / rec.outside_ior
+        };
 
         let unit_direction = Vec3::unit_vector(&ray_in.direction);
         let cos_theta: f64 = f64::min(Vec3::dot(&-unit_direction, &rec.normal), 1.0);
@@ -261,8 +521,10 @@ impl Dielectric {
         let cannot_refract: bool = ri * sin_theta > 1.0;
 
         let direction: Vec3 = if cannot_refract || Dielectric::reflectance(cos_theta, ri) > random_f64() {
+            srec.attenuation = Color::new(1.0, 1.0, 1.0);
             Vec3::reflect(&unit_direction, &rec.normal)
         } else {
+            srec.attenuation = self.tint.value(rec.u, rec.v, &rec.point);
             Vec3::refract(&unit_direction, &rec.normal, ri)
         };
 
@@ -273,34 +535,105 @@ impl Dielectric {
 
 // ----- Diffuse Light Material -----
 
+/// Which faces of a surface emit light, relative to its outward normal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmissionSides {
+    Front,
+    Back,
+    Both,
+}
+
 /// A Diffuse Light material defined by its emission texture.
 #[derive(Clone)]
 pub struct DiffuseLight {
     tex: Arc<Texture>,
+    sides: EmissionSides, // Faces that emit, front only by default
+    intensity: f64,       // Scale applied to the emission color
+    focus: f64,           // Cosine power of the emission profile, 0 for a Lambertian emitter
 }
 
 impl DiffuseLight {
     /// Constructor from a Color.
     pub fn new(emit_color: Color) -> Self {
-        Self { 
-            tex: Arc::new(Texture::from(SolidColor::new(emit_color))),
-        }
+        Self::from_texture(Arc::new(Texture::from(SolidColor::new(emit_color))))
     }
 
     /// Constructor from a Texture reference counter.
     pub fn from_texture(tex: Arc<Texture>) -> Self {
-        Self { 
+        Self {
             tex,
+            sides: EmissionSides::Front,
+            intensity: 1.0,
+            focus: 0.0,
+        }
+    }
+
+    /// Constructor for a blackbody emitter at the given temperature in Kelvin, with unit luminance.
+    /// Combine with `with_power` to set its brightness in physical units.
+    pub fn blackbody(kelvin: f64) -> Self {
+        Self::new(photometry::blackbody(kelvin))
+    }
+
+    /// Set which faces emit light, e.g. `EmissionSides::Both` for a double-sided panel.
+    pub fn with_sides(mut self, sides: EmissionSides) -> Self {
+        self.sides = sides;
+        self
+    }
+
+    /// Set the intensity (power) scale, separate from the emission color.
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity.max(0.0);
+        self
+    }
+
+    /// Set a focused, spot-like emission profile `cos^focus`. The profile is normalized so the
+    /// total emitted power matches a Lambertian emitter of the same color and intensity.
+    pub fn with_focus(mut self, focus: f64) -> Self {
+        self.focus = focus.max(0.0);
+        self
+    }
+
+    /// Set the intensity from a physical power for an emitter of the given area, e.g. `Quad::area`.
+    /// The emission color is treated as a chromaticity and normalized by its luminance, and two-sided
+    /// emitters split the power over both faces. Call after `with_sides`.
+    pub fn with_power(mut self, power: LightPower, area: f64) -> Self {
+        let emitting_area = match self.sides {
+            EmissionSides::Both => 2.0 * area,
+            _ => area,
+        };
+        let luminance = self.tex.value(0.5, 0.5, &Point3::zero()).luminance();
+        self.intensity = if luminance > 0.0 { power.radiance(emitting_area) / luminance } else { 0.0 };
+        self
+    }
+
+    /// The emission texture, unless it is a constant color.
+    fn textured_emission(&self) -> Option<&Arc<Texture>> {
+        match &*self.tex {
+            Texture::SolidColor(_) => None,
+            _ => Some(&self.tex),
         }
     }
 
     /// Emitted light function.
     #[inline]
-    fn emitted(&self, _ray_in: &Ray, rec: &HitRecord, u: f64, v: f64, point: &Point3) -> Color {
-        if !rec.front_face {
-            return Color::zero(); // No emission from the back face
+    fn emitted(&self, ray_in: &Ray, rec: &HitRecord, u: f64, v: f64, point: &Point3) -> Color {
+        let emits = match self.sides {
+            EmissionSides::Front => rec.front_face,
+            EmissionSides::Back => !rec.front_face,
+            EmissionSides::Both => true,
+        };
+        if !emits {
+            return Color::zero();
         }
-        self.tex.value(u, v, point)
+
+        let radiance = self.intensity * self.tex.value(u, v, point);
+        if self.focus <= 0.0 {
+            return radiance;
+        }
+
+        // rec.normal always faces the incoming ray, so this is the cosine to the emitting face.
+        let cos_theta = Vec3::dot(&-Vec3::unit_vector(&ray_in.direction), &rec.normal).max(0.0);
+        radiance * (0.5 * (self.focus + 2.0) * cos_theta.powf(self.focus))
     }
 }
 
@@ -339,4 +672,587 @@ impl Isotropic {
     fn scattering_pdf(&self, _ray_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
         1.0 / (4.0 * PI) // Uniform scattering in all directions
     }
-}
\ No newline at end of file
+}
+
+// ----- Henyey-Greenstein (anisotropic scattering) Material -----
+
+/// A phase function for participating media that favors scattering forward (or backward), as fog,
+/// clouds and smoke do. Either a single Henyey-Greenstein lobe or a blend of two (double HG),
+/// which fits media with both a strong forward peak and some back-scattering.
+#[derive(Clone)]
+pub struct HenyeyGreenstein {
+    tex: Arc<Texture>,
+    g: [f64; 2],  // Asymmetry of each lobe, in (-1,1)
+    weight: f64,  // Fraction of scattering in the first lobe
+}
+
+impl HenyeyGreenstein {
+    /// Constructor for a single lobe from a color and asymmetry `g`.
+    pub fn new(albedo: Color, g: f64) -> Self {
+        Self::double(albedo, g, g, 1.0)
+    }
+
+    /// Constructor for a blend of two lobes, with `weight` of the scattering in the `g1` lobe.
+    pub fn double(albedo: Color, g1: f64, g2: f64, weight: f64) -> Self {
+        Self::from_texture(Arc::new(Texture::from(SolidColor::new(albedo))), g1, g2, weight)
+    }
+
+    /// Constructor for a blend of two lobes from a texture.
+    pub fn from_texture(tex: Arc<Texture>, g1: f64, g2: f64, weight: f64) -> Self {
+        let g1 = g1.clamp(-phase::MAX_ASYMMETRY, phase::MAX_ASYMMETRY);
+        let g2 = g2.clamp(-phase::MAX_ASYMMETRY, phase::MAX_ASYMMETRY);
+        Self { tex, g: [g1, g2], weight: weight.clamp(0.0, 1.0) }
+    }
+
+    /// Scatter method for Henyey-Greenstein material. Sampling follows the phase function exactly.
+    #[inline]
+    fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        srec.attenuation = self.tex.value(rec.u, rec.v, &rec.point);
+        srec.pdf_ptr = Some(PDF::henyey_greenstein(&ray_in.direction, self.g, self.weight));
+        srec.skip_pdf = false;
+        true
+    }
+
+    /// Scattering PDF for Henyey-Greenstein material: the phase function of the scattering angle.
+    #[inline]
+    fn scattering_pdf(&self, ray_in: &Ray, _rec: &HitRecord, scattered: &Ray) -> f64 {
+        let cos_theta = Vec3::dot(&Vec3::unit_vector(&ray_in.direction), &Vec3::unit_vector(&scattered.direction));
+        self.weight * phase::henyey_greenstein(cos_theta, self.g[0])
+            + (1.0 - self.weight) * phase::henyey_greenstein(cos_theta, self.g[1])
+    }
+}
+
+// ----- Principled (Disney-style) Material -----
+
+/// A principled "uber" material spanning plastic, metal, glass and fabric.
+/// Follows the Disney BRDF: a diffuse lobe with a subsurface approximation, sheen,
+/// a GGX specular lobe and a GTR1 clearcoat lobe, plus a stochastic glass lobe for transmission.
+/// Every parameter is driven by a texture; scalar parameters read the mean of the color channels.
+#[derive(Clone)]
+pub struct Principled {
+    base_color: Arc<Texture>,
+    metallic: Arc<Texture>,
+    roughness: Arc<Texture>,
+    specular: Arc<Texture>,
+    specular_tint: Arc<Texture>,
+    sheen: Arc<Texture>,
+    sheen_tint: Arc<Texture>,
+    clearcoat: Arc<Texture>,
+    clearcoat_gloss: Arc<Texture>,
+    transmission: Arc<Texture>,
+    ior: Arc<Texture>,
+    subsurface: Arc<Texture>,
+}
+
+/// Principled parameters evaluated at a single hit point.
+struct PrincipledParams {
+    base_color: Color,
+    metallic: f64,
+    roughness: f64,
+    specular: f64,
+    specular_tint: f64,
+    sheen: f64,
+    sheen_tint: f64,
+    clearcoat: f64,
+    clearcoat_gloss: f64,
+    transmission: f64,
+    ior: f64,
+    subsurface: f64,
+}
+
+impl PrincipledParams {
+    /// Probability of taking the glass (transmission) lobe.
+    #[inline]
+    fn glass_probability(&self) -> f64 {
+        self.transmission * (1.0 - self.metallic)
+    }
+
+    /// Hue of the base color with unit luminance, used by the tint parameters.
+    #[inline]
+    fn tint(&self) -> Color {
+        let luminance = self.base_color.luminance();
+        if luminance > 0.0 { self.base_color / luminance } else { Color::new(1.0, 1.0, 1.0) }
+    }
+}
+
+impl Principled {
+    /// Constructor from a base Color, with a plastic-like default parameter set.
+    pub fn new(base_color: Color) -> Self {
+        Self::from_texture(Texture::solid(base_color))
+    }
+
+    /// Constructor from a base color Texture, with a plastic-like default parameter set.
+    /// - Metallic: 0, Roughness: 0.5, Specular: 0.5, Specular Tint: 0
+    /// - Sheen: 0, Sheen Tint: 0.5, Clearcoat: 0, Clearcoat Gloss: 1
+    /// - Transmission: 0, IOR: 1.5, Subsurface: 0
+    pub fn from_texture(base_color: Arc<Texture>) -> Self {
+        Self {
+            base_color,
+            metallic: Texture::scalar(0.0),
+            roughness: Texture::scalar(0.5),
+            specular: Texture::scalar(0.5),
+            specular_tint: Texture::scalar(0.0),
+            sheen: Texture::scalar(0.0),
+            sheen_tint: Texture::scalar(0.5),
+            clearcoat: Texture::scalar(0.0),
+            clearcoat_gloss: Texture::scalar(1.0),
+            transmission: Texture::scalar(0.0),
+            ior: Texture::scalar(1.5),
+            subsurface: Texture::scalar(0.0),
+        }
+    }
+
+    /// Set the metallic blend in [0,1]: 0 is dielectric, 1 is a conductor tinted by the base color.
+    pub fn with_metallic(mut self, tex: Arc<Texture>) -> Self { self.metallic = tex; self }
+    /// Set the perceptual roughness in [0,1] shared by the diffuse, specular and glass lobes.
+    pub fn with_roughness(mut self, tex: Arc<Texture>) -> Self { self.roughness = tex; self }
+    /// Set the dielectric specular amount in [0,1]; 0.5 corresponds to a 4% normal reflectance.
+    pub fn with_specular(mut self, tex: Arc<Texture>) -> Self { self.specular = tex; self }
+    /// Set how much the dielectric specular is tinted towards the base color hue.
+    pub fn with_specular_tint(mut self, tex: Arc<Texture>) -> Self { self.specular_tint = tex; self }
+    /// Set the grazing-angle sheen amount, mostly useful for cloth.
+    pub fn with_sheen(mut self, tex: Arc<Texture>) -> Self { self.sheen = tex; self }
+    /// Set how much the sheen is tinted towards the base color hue.
+    pub fn with_sheen_tint(mut self, tex: Arc<Texture>) -> Self { self.sheen_tint = tex; self }
+    /// Set the strength of the secondary clearcoat lobe in [0,1].
+    pub fn with_clearcoat(mut self, tex: Arc<Texture>) -> Self { self.clearcoat = tex; self }
+    /// Set the clearcoat glossiness: 0 is satin, 1 is a high gloss finish.
+    pub fn with_clearcoat_gloss(mut self, tex: Arc<Texture>) -> Self { self.clearcoat_gloss = tex; self }
+    /// Set the glass-like transmission amount in [0,1].
+    pub fn with_transmission(mut self, tex: Arc<Texture>) -> Self { self.transmission = tex; self }
+    /// Set the index of refraction used by the transmission lobe.
+    pub fn with_ior(mut self, tex: Arc<Texture>) -> Self { self.ior = tex; self }
+    /// Set the blend from the diffuse lobe to the flattened subsurface approximation.
+    pub fn with_subsurface(mut self, tex: Arc<Texture>) -> Self { self.subsurface = tex; self }
+
+    /// Evaluate all parameter textures at the hit point.
+    #[inline]
+    fn params(&self, rec: &HitRecord) -> PrincipledParams {
+        let (u, v, p) = (rec.u, rec.v, &rec.point);
+        PrincipledParams {
+            base_color: self.base_color.value(u, v, p),
+            metallic: self.metallic.scalar_value(u, v, p).clamp(0.0, 1.0),
+            roughness: self.roughness.scalar_value(u, v, p).clamp(0.0, 1.0),
+            specular: self.specular.scalar_value(u, v, p).max(0.0),
+            specular_tint: self.specular_tint.scalar_value(u, v, p).clamp(0.0, 1.0),
+            sheen: self.sheen.scalar_value(u, v, p).max(0.0),
+            sheen_tint: self.sheen_tint.scalar_value(u, v, p).clamp(0.0, 1.0),
+            clearcoat: self.clearcoat.scalar_value(u, v, p).max(0.0),
+            clearcoat_gloss: self.clearcoat_gloss.scalar_value(u, v, p).clamp(0.0, 1.0),
+            transmission: self.transmission.scalar_value(u, v, p).clamp(0.0, 1.0),
+            ior: self.ior.scalar_value(u, v, p).max(1.0),
+            subsurface: self.subsurface.scalar_value(u, v, p).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Clearcoat GTR1 alpha from the clearcoat gloss parameter.
+    #[inline]
+    fn clearcoat_alpha(params: &PrincipledParams) -> f64 {
+        0.1 + (0.001 - 0.1) * params.clearcoat_gloss
+    }
+
+    /// Scatter method for a Principled material.
+    /// The glass lobe is sampled implicitly (like `Dielectric`), all other lobes go through the PDF path.
+    #[inline]
+    fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        let params = self.params(rec);
+        let wo = -Vec3::unit_vector(&ray_in.direction);
+        let alpha = microfacet::roughness_to_alpha(params.roughness);
+
+        // Inside a transmissive object only the glass lobe is meaningful.
+        let glass_probability = params.glass_probability();
+        if glass_probability > 0.0 && (!rec.front_face || random_f64() < glass_probability) {
+            let eta = if rec.front_face { 1.0 / params.ior } else { params.ior };
+
+            // Sample a microfacet normal, falling back to the shading normal if it faces away from the viewer.
+            let mut h = microfacet::sample_ggx_half(&ONB::new(&rec.normal), alpha);
+            if Vec3::dot(&wo, &h) <= 0.0 {
+                h = rec.normal;
+            }
+
+            let fresnel = microfacet::fresnel_dielectric(Vec3::dot(&wo, &h), eta);
+            let (direction, attenuation) = if random_f64() < fresnel {
+                (Vec3::reflect(&-wo, &h), Color::new(1.0, 1.0, 1.0))
+            } else {
+                (Vec3::refract(&-wo, &h, eta), params.base_color)
+            };
+
+            srec.attenuation = attenuation;
+            srec.pdf_ptr = None;
+            srec.skip_pdf = true;
+            srec.skip_pdf_ray = Ray::new_with_time(rec.point, direction, ray_in.time);
+            return true;
+        }
+
+        // Lobe selection weights; the specular lobe is always present.
+        let diffuse_weight = (1.0 - params.metallic) * (1.0 - params.transmission);
+        let clearcoat_weight = 0.25 * params.clearcoat * (1.0 - glass_probability);
+
+        srec.attenuation = Color::new(1.0, 1.0, 1.0); // Full response is evaluated in bsdf()
+        srec.pdf_ptr = Some(PDF::principled(
+            &rec.normal,
+            &wo,
+            alpha,
+            Self::clearcoat_alpha(&params),
+            [diffuse_weight, 1.0, clearcoat_weight],
+        ));
+        srec.skip_pdf = false;
+        true
+    }
+
+    /// BSDF times cosine of the non-glass lobes, divided by the probability of not taking the glass lobe.
+    #[inline]
+    fn bsdf(&self, ray_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        let wo = -Vec3::unit_vector(&ray_in.direction);
+        let wi = Vec3::unit_vector(&scattered.direction);
+        let n_dot_l = Vec3::dot(&rec.normal, &wi);
+        let n_dot_v = Vec3::dot(&rec.normal, &wo);
+        if n_dot_l <= 0.0 || n_dot_v <= 0.0 {
+            return Color::zero();
+        }
+
+        let params = self.params(rec);
+        let non_glass_probability = 1.0 - params.glass_probability();
+        if non_glass_probability <= 0.0 {
+            return Color::zero();
+        }
+
+        let h = Vec3::unit_vector(&(wi + wo));
+        let n_dot_h = Vec3::dot(&rec.normal, &h);
+        let l_dot_h = Vec3::dot(&wi, &h);
+
+        let white = Color::new(1.0, 1.0, 1.0);
+        let tint = params.tint();
+        let fl = microfacet::schlick_weight(n_dot_l);
+        let fv = microfacet::schlick_weight(n_dot_v);
+        let fh = microfacet::schlick_weight(l_dot_h);
+
+        // Diffuse with retro-reflection, blended towards the Hanrahan-Krueger subsurface approximation.
+        let fd90 = 0.5 + 2.0 * l_dot_h * l_dot_h * params.roughness;
+        let fd = (1.0 + (fd90 - 1.0) * fl) * (1.0 + (fd90 - 1.0) * fv);
+        let fss90 = l_dot_h * l_dot_h * params.roughness;
+        let fss = (1.0 + (fss90 - 1.0) * fl) * (1.0 + (fss90 - 1.0) * fv);
+        let ss = 1.25 * (fss * (1.0 / (n_dot_l + n_dot_v) - 0.5) + 0.5);
+        let diffuse = params.base_color * ((fd + (ss - fd) * params.subsurface) / PI);
+
+        // Sheen
+        let sheen_color = mix(white, tint, params.sheen_tint);
+        let sheen = fh * params.sheen * sheen_color;
+
+        // GGX specular. The glass lobe has its own Fresnel reflection, so the dielectric part only
+        // covers the share of the surface that does not transmit.
+        let dielectric_weight = (1.0 - params.metallic) * (1.0 - params.transmission);
+        let alpha = microfacet::roughness_to_alpha(params.roughness);
+        let specular_f0 = 0.08 * params.specular * mix(white, tint, params.specular_tint);
+        let specular_fresnel = dielectric_weight * microfacet::schlick_fresnel(specular_f0, l_dot_h)
+            + params.metallic * microfacet::schlick_fresnel(params.base_color, l_dot_h);
+        let specular = specular_fresnel
+            * (microfacet::ggx_d(n_dot_h, alpha) * microfacet::smith_g(n_dot_l, n_dot_v, alpha) / (4.0 * n_dot_l * n_dot_v));
+
+        // GTR1 clearcoat with a fixed 1.5 IOR (F0 = 0.04) and fixed masking roughness, over the
+        // share of the surface not taken by the glass lobe
+        let clearcoat_fresnel = 0.04 + 0.96 * fh;
+        let clearcoat = 0.25 * params.clearcoat * non_glass_probability * clearcoat_fresnel
+            * microfacet::gtr1_d(n_dot_h, Self::clearcoat_alpha(&params))
+            * microfacet::smith_g(n_dot_l, n_dot_v, 0.25) / (4.0 * n_dot_l * n_dot_v);
+
+        let f = dielectric_weight * (diffuse + sheen) + specular + clearcoat * white;
+
+        f * (n_dot_l / non_glass_probability)
+    }
+}
+
+// ----- Coated (layered) Material -----
+
+/// A layered material: a dielectric Fresnel coat over any base material (varnish, car paint, glossy plastic).
+/// The coat reflects with probability equal to its Fresnel reflectance, otherwise light passes through
+/// the coat (with optional absorption) and scatters off the base. The coat is treated as infinitely thin,
+/// so the base sees unrefracted directions.
+#[derive(Clone)]
+pub struct Coated {
+    base: Arc<Material>,
+    ior: f64,
+    roughness: f64,
+    absorption: Color, // Absorption coefficient of the coat per unit thickness
+    thickness: f64,
+}
+
+impl Coated {
+    /// Constructor for a smooth, clear coat of refractive index `ior` over `base`.
+    pub fn new(base: Arc<Material>, ior: f64) -> Self {
+        Self {
+            base,
+            ior: ior.max(1.0),
+            roughness: 0.0,
+            absorption: Color::zero(),
+            thickness: 0.0,
+        }
+    }
+
+    /// Set the coat roughness in [0,1]; 0 is a perfect mirror coat.
+    pub fn with_roughness(mut self, roughness: f64) -> Self {
+        self.roughness = roughness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set a tinted coat by its absorption coefficient and thickness, attenuating light by Beer-Lambert's law.
+    pub fn with_absorption(mut self, absorption: Color, thickness: f64) -> Self {
+        self.absorption = absorption;
+        self.thickness = thickness.max(0.0);
+        self
+    }
+
+    /// Fresnel reflectance of the coat for light arriving at `cosine` from the outside.
+    #[inline]
+    fn fresnel(&self, cosine: f64) -> f64 {
+        microfacet::fresnel_dielectric(cosine, 1.0 / self.ior)
+    }
+
+    /// Coat transmittance for a single pass at `cosine` from the outside, following the refracted path length.
+    #[inline]
+    fn transmittance(&self, cosine: f64) -> Color {
+        if self.thickness <= 0.0 {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+        let sin2_t = (1.0 - cosine * cosine) / (self.ior * self.ior);
+        let cos_t = (1.0 - sin2_t).max(EPSILON).sqrt();
+        (-self.thickness / cos_t * self.absorption).exp()
+    }
+
+    /// Scatter method for a Coated material.
+    #[inline]
+    fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        // Back faces are not coated, e.g. the inside of a coated glass object.
+        if !rec.front_face {
+            return self.base.scatter(ray_in, rec, srec);
+        }
+
+        let wo = -Vec3::unit_vector(&ray_in.direction);
+        let cos_o = Vec3::dot(&rec.normal, &wo).clamp(0.0, 1.0);
+
+        // Choose the coat lobe with its Fresnel probability at a microfacet visible from the viewer,
+        // so the Fresnel term cancels. A rough coat's reflection keeps the masking-shadowing weight
+        // G2/G1 of visible normal sampling, and is lost where it leaves below the surface.
+        let alpha = microfacet::roughness_to_alpha(self.roughness);
+        let h = if self.roughness > 0.0 { microfacet::sample_ggx_visible_half(&ONB::new(&rec.normal), &wo, alpha) } else { rec.normal };
+        if random_f64() < self.fresnel(Vec3::dot(&wo, &h).clamp(0.0, 1.0)) {
+            let direction = Vec3::reflect(&-wo, &h);
+            let cos_i = Vec3::dot(&direction, &rec.normal);
+            if cos_i <= 0.0 {
+                return false;
+            }
+            let masking = if self.roughness > 0.0 { microfacet::smith_g(cos_i, cos_o, alpha) / microfacet::smith_g1(cos_o, alpha) } else { 1.0 };
+            if !masking.is_finite() {
+                return false;
+            }
+
+            srec.attenuation = Color::new(masking, masking, masking);
+            srec.pdf_ptr = None;
+            srec.skip_pdf = true;
+            srec.skip_pdf_ray = Ray::new_with_time(rec.point, direction, ray_in.time);
+            return true;
+        }
+
+        // Otherwise light enters the coat and scatters off the base.
+        if !self.base.scatter(ray_in, rec, srec) {
+            return false;
+        }
+
+        // Implicitly sampled bases: apply the coat's exit terms now since the direction is known.
+        // Non-specular bases get the same terms in bsdf().
+        if srec.skip_pdf {
+            let wi = Vec3::unit_vector(&srec.skip_pdf_ray.direction);
+            let cos_i = Vec3::dot(&rec.normal, &wi);
+            let mut weight = self.transmittance(cos_o);
+            if cos_i > 0.0 {
+                weight = weight * self.transmittance(cos_i) * (1.0 - self.fresnel(cos_i));
+            }
+            srec.attenuation = srec.attenuation * weight;
+        }
+        true
+    }
+
+    /// Base response attenuated by the coat: absorption on both passes and Fresnel transmission on the way out.
+    /// Fresnel transmission on the way in is accounted for by the lobe selection in scatter().
+    #[inline]
+    fn bsdf(&self, ray_in: &Ray, rec: &HitRecord, srec: &ScatterRecord, scattered: &Ray) -> Color {
+        let base_bsdf = self.base.scattering_bsdf(ray_in, rec, srec, scattered);
+        if !rec.front_face {
+            return base_bsdf;
+        }
+
+        let cos_o = Vec3::dot(&rec.normal, &-Vec3::unit_vector(&ray_in.direction)).clamp(0.0, 1.0);
+        let cos_i = Vec3::dot(&rec.normal, &Vec3::unit_vector(&scattered.direction));
+        if cos_i <= 0.0 {
+            return base_bsdf * self.transmittance(cos_o);
+        }
+
+        base_bsdf * self.transmittance(cos_o) * self.transmittance(cos_i) * (1.0 - self.fresnel(cos_i))
+    }
+}
+
+// ----- Tinted (scaled) Material -----
+
+/// A base material whose scattered light is scaled per color channel, e.g. a phase function
+/// weighted by a chromatic medium's scattering coefficients. Emission is not tinted.
+#[derive(Clone)]
+pub struct Tinted {
+    base: Arc<Material>,
+    tint: Color,
+}
+
+impl Tinted {
+    /// Constructor from a base material and the per-channel scale of its scattered light.
+    pub fn new(base: Arc<Material>, tint: Color) -> Self {
+        Self { base, tint }
+    }
+
+    /// Scatter method for a Tinted material.
+    #[inline]
+    fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        if !self.base.scatter(ray_in, rec, srec) {
+            return false;
+        }
+
+        // Implicitly sampled bases are tinted now, non-specular ones in bsdf().
+        if srec.skip_pdf {
+            srec.attenuation = srec.attenuation * self.tint;
+        }
+        true
+    }
+
+    /// Base response scaled by the tint.
+    #[inline]
+    fn bsdf(&self, ray_in: &Ray, rec: &HitRecord, srec: &ScatterRecord, scattered: &Ray) -> Color {
+        self.tint * self.base.scattering_bsdf(ray_in, rec, srec, scattered)
+    }
+}
+
+// ----- Passthrough (invisible) Material -----
+
+/// An invisible surface: rays continue in the same direction, scaled by a tint, e.g. the boundary
+/// of a volume that only holds a medium.
+#[derive(Clone)]
+pub struct Passthrough {
+    tint: Color,
+}
+
+impl Passthrough {
+    /// Constructor from the per-channel weight applied to rays passing through.
+    pub fn new(tint: Color) -> Self {
+        Self { tint }
+    }
+
+    /// Scatter method for a Passthrough material: continue the ray unchanged.
+    #[inline]
+    fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        srec.attenuation = self.tint;
+        srec.pdf_ptr = None;
+        srec.skip_pdf = true;
+        srec.skip_pdf_ray = Ray::new_with_time(rec.point, ray_in.direction, ray_in.time);
+        true
+    }
+}
+
+// ----- Interior (enclosed volume) Material -----
+
+/// A closed surface bounding a volume, e.g. a glass holding a liquid or the walls of a foggy room.
+/// The surface scatters as usual, while the integrator keeps track of the volumes a ray is inside
+/// to apply the enclosed medium and the refractive index on each side of nested dielectrics.
+/// The surface normals must point outwards, and each volume needs its own material instance.
+#[derive(Clone)]
+pub struct Interior {
+    surface: Arc<Material>,
+    medium: Option<Arc<Medium>>,
+    priority: u32, // Where volumes overlap, the highest priority one applies
+}
+
+impl Interior {
+    /// Constructor from a surface material and the medium it encloses, or None for a clear volume
+    /// that only takes part in the nesting, such as a glass wall.
+    pub fn new(surface: Arc<Material>, medium: Option<Medium>) -> Self {
+        Self { surface, medium: medium.map(Arc::new), priority: 0 }
+    }
+
+    /// Set the priority deciding which volume applies where volumes overlap. Surfaces of a volume
+    /// inside a higher priority one are ignored, so liquid can overlap the glass wall holding it.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The enclosed medium, if any.
+    pub fn medium(&self) -> Option<&Arc<Medium>> {
+        self.medium.as_ref()
+    }
+
+    /// Priority of the volume where it overlaps others.
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+}
+
+// ----- Subsurface (random walk surface) Material -----
+
+/// The surface of a subsurface scattering volume: a smooth dielectric reflection over a rough
+/// interface that lets light in and out of the enclosed medium diffusely. Leaving diffusely lets
+/// the integrator sample lights where the random walk exits, rather than only by refraction.
+/// From inside, the walk is reflected back into the medium with the interior Fresnel reflectance.
+#[derive(Clone)]
+pub struct Subsurface {
+    refraction_index: f64,
+}
+
+impl Subsurface {
+    /// Constructor from the refractive index of the surface coat.
+    pub fn new(refraction_index: f64) -> Self {
+        Self { refraction_index }
+    }
+
+    /// Scatter method for a Subsurface surface. Reflect specularly with the Fresnel probability,
+    /// or totally past the critical angle from inside; otherwise enter the medium, or leave it,
+    /// with a cosine lobe.
+    #[inline]
+    fn scatter(&self, ray_in: &Ray, rec: &HitRecord, srec: &mut ScatterRecord) -> bool {
+        srec.attenuation = Color::new(1.0, 1.0, 1.0);
+        let unit_direction = Vec3::unit_vector(&ray_in.direction);
+        let cos_theta = f64::min(Vec3::dot(&-unit_direction, &rec.normal), 1.0);
+        let ri = if rec.front_face { rec.outside_ior / self.refraction_index } else { self.refraction_index / rec.outside_ior };
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let reflects = ri * sin_theta > 1.0 || Dielectric::reflectance(cos_theta, ri) > random_f64();
+
+        if !rec.front_face && !reflects {
+            srec.pdf_ptr = Some(PDF::cosine(&-rec.normal));
+            srec.skip_pdf = false;
+            return true;
+        }
+
+        srec.pdf_ptr = None;
+        srec.skip_pdf = true;
+
+        let direction = if reflects {
+            Vec3::reflect(&unit_direction, &rec.normal)
+        } else {
+            ONB::new(&-rec.normal).transform(&Vec3::random_cosine_direction())
+        };
+
+        srec.skip_pdf_ray = Ray::new_with_time(rec.point, direction, ray_in.time);
+        true
+    }
+
+    /// Scattering PDF for a Subsurface surface: the cosine lobe leaving the medium, seen from inside.
+    #[inline]
+    fn scattering_pdf(&self, _ray_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        if rec.front_face {
+            return 0.0;
+        }
+        let cos_theta = Vec3::dot(&-rec.normal, &Vec3::unit_vector(&scattered.direction));
+        if cos_theta < 0.0 { 0.0 } else { cos_theta / PI }
+    }
+}
+
+/// Linear interpolation between two colors.
+#[inline]
+fn mix(a: Color, b: Color, t: f64) -> Color {
+    a + t * (b - a)
+}
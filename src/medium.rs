@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::prelude::*;
+
+// Homogeneous media filling the inside of closed surfaces, tracked by the integrator with a stack
+// of the volumes a ray is inside. Unlike `ConstantMedium`, the boundary may be any closed surface
+// (non-convex meshes, glass), since the integrator rather than the boundary decides where the
+// ray travels through the medium. `ConstantMedium` wraps the same model in a convex boundary.
+
+// ----- Medium -----
+
+/// A homogeneous participating medium with per-channel absorption and scattering coefficients.
+#[derive(Clone)]
+pub struct Medium {
+    sigma_a: Color, // Absorption coefficient per channel
+    sigma_s: Color, // Scattering coefficient per channel
+    emission: Color, // Radiance emitted by absorbing particles
+    phase_function: Arc<Material>,
+}
+
+/// Outcome of sampling a distance through a medium, with the per-channel weight of the choice.
+pub enum MediumSample {
+    Scatter { distance: f64, weight: Color },
+    Pass { weight: Color },
+}
+
+/// First real collision of a ray tracked through a medium.
+pub enum MediumEvent {
+    Scatter { distance: f64 },
+    Absorb { distance: f64 },
+    Pass,
+}
+
+impl Medium {
+    /// Constructor from absorption and scattering coefficients per color channel (per unit length),
+    /// with a white isotropic phase function.
+    pub fn new(sigma_a: Color, sigma_s: Color) -> Self {
+        Self {
+            sigma_a: Color::new(sigma_a.x().max(0.0), sigma_a.y().max(0.0), sigma_a.z().max(0.0)),
+            sigma_s: Color::new(sigma_s.x().max(0.0), sigma_s.y().max(0.0), sigma_s.z().max(0.0)),
+            emission: Color::zero(),
+            phase_function: Material::isotropic(Color::new(1.0, 1.0, 1.0)),
+        }
+    }
+
+    /// Constructor for a clear absorbing medium, e.g. colored liquid or tinted glass.
+    pub fn absorbing(sigma_a: Color) -> Self {
+        Self::new(sigma_a, Color::zero())
+    }
+
+    /// Constructor for subsurface scattering from the color a thick slab appears and the mean free
+    /// path per channel, i.e. how far light of each color travels between collisions. The surface
+    /// albedo results from many scattering events, so it is inverted to the single-scattering albedo
+    /// with van de Hulst's approximation for a semi-infinite medium.
+    pub fn subsurface(albedo: Color, mean_free_path: Color) -> Self {
+        let single = |albedo: f64| {
+            let a = albedo.clamp(0.0, 0.999);
+            let s = 4.09712 + 4.20863 * a - (9.59217 + 41.6808 * a + 17.7126 * a * a).sqrt();
+            1.0 - s * s
+        };
+        let extinction = |mfp: f64| 1.0 / mfp.max(EPSILON);
+        let sigma_t = Color::new(extinction(mean_free_path.x()), extinction(mean_free_path.y()), extinction(mean_free_path.z()));
+        let sigma_s = sigma_t * Color::new(single(albedo.x()), single(albedo.y()), single(albedo.z()));
+        Self::new(sigma_t - sigma_s, sigma_s)
+    }
+
+    /// Replace the isotropic phase function, e.g. with a forward-scattering Henyey-Greenstein one.
+    pub fn with_phase_function(mut self, phase_function: Arc<Material>) -> Self {
+        self.phase_function = phase_function;
+        self
+    }
+
+    /// Make the medium glow. Emission comes from the absorbing particles.
+    pub fn with_emission(mut self, emission: Color) -> Self {
+        self.emission = emission;
+        self
+    }
+
+    /// The phase function applied at scattering events.
+    pub fn phase_function(&self) -> &Arc<Material> {
+        &self.phase_function
+    }
+
+    /// Radiance emitted by the absorbing particles.
+    pub fn emission(&self) -> Color {
+        self.emission
+    }
+
+    /// Fraction of light per channel surviving `distance` through the medium (Beer-Lambert).
+    pub fn transmittance(&self, distance: f64) -> Color {
+        let sigma_t = self.sigma_a + self.sigma_s;
+        Color::new(
+            channel_transmittance(sigma_t.x(), distance),
+            channel_transmittance(sigma_t.y(), distance),
+            channel_transmittance(sigma_t.z(), distance),
+        )
+    }
+
+    /// Emitted radiance accumulated over `distance` through the medium, attenuated on the way.
+    pub fn emitted(&self, distance: f64) -> Color {
+        if self.emission.near_zero() {
+            return Color::zero();
+        }
+        let sigma_t = self.sigma_a + self.sigma_s;
+        let integral = |sigma_t: f64, sigma_a: f64| {
+            if sigma_t > 0.0 { sigma_a * (1.0 - channel_transmittance(sigma_t, distance)) / sigma_t } else { 0.0 }
+        };
+        self.emission * Color::new(
+            integral(sigma_t.x(), self.sigma_a.x()),
+            integral(sigma_t.y(), self.sigma_a.y()),
+            integral(sigma_t.z(), self.sigma_a.z()),
+        )
+    }
+
+    /// Sample where a ray travelling `distance` (possibly infinite) through the medium scatters.
+    /// The distance is drawn from a randomly chosen channel's extinction and weighted by the
+    /// average density over all channels, so chromatic media stay unbiased in every channel.
+    pub fn sample(&self, distance: f64) -> MediumSample {
+        let sigma_t = self.sigma_a + self.sigma_s;
+        let channel = sigma_t[random_usize(0, 2)];
+        let sampled = if channel > 0.0 { -(1.0 - random_f64()).ln() / channel } else { f64::INFINITY };
+
+        if sampled < distance {
+            let transmittance = self.transmittance(sampled);
+            let pdf = mean(&(sigma_t * transmittance));
+            if pdf <= 0.0 {
+                return MediumSample::Pass { weight: Color::zero() };
+            }
+            MediumSample::Scatter { distance: sampled, weight: self.sigma_s * transmittance / pdf }
+        } else {
+            let transmittance = self.transmittance(distance);
+            let probability = mean(&transmittance);
+            if probability <= 0.0 {
+                return MediumSample::Pass { weight: Color::zero() };
+            }
+            MediumSample::Pass { weight: transmittance / probability }
+        }
+    }
+
+    /// Find where a ray travelling `distance` through the medium first scatters or absorbs, with
+    /// spectral tracking: each event is picked with the channel-averaged share of its coefficient
+    /// and weighted per channel by the ratio of the channel's coefficient to that average (see
+    /// `scatter_weight` and `absorb_weight`). Null
+    /// collisions against the largest channel's extinction only reweight the channels, so they are
+    /// skipped: the first real collision lies at the average extinction, and the expected weight of
+    /// the null collisions on the way is `tracking_weight`, which the caller applies to whatever
+    /// the ray reaches. A gray medium has no null collisions.
+    pub fn track(&self, distance: f64) -> MediumEvent {
+        let (mean_a, mean_s) = (mean(&self.sigma_a), mean(&self.sigma_s));
+        if mean_a + mean_s <= 0.0 {
+            return MediumEvent::Pass;
+        }
+        let travelled = -(1.0 - random_f64()).ln() / (mean_a + mean_s);
+        if travelled >= distance {
+            return MediumEvent::Pass;
+        }
+
+        if random_f64() * (mean_a + mean_s) < mean_s {
+            MediumEvent::Scatter { distance: travelled }
+        } else {
+            MediumEvent::Absorb { distance: travelled }
+        }
+    }
+
+    /// Per-channel weight of a scattering event from `track`.
+    pub fn scatter_weight(&self) -> Color {
+        relative(&self.sigma_s)
+    }
+
+    /// Per-channel weight of an absorption event from `track`.
+    pub fn absorb_weight(&self) -> Color {
+        relative(&self.sigma_a)
+    }
+
+    /// Whether every channel has the same extinction, so tracking needs no channel weights.
+    pub fn is_gray(&self) -> bool {
+        let sigma_t = self.sigma_a + self.sigma_s;
+        sigma_t.x() == sigma_t.y() && sigma_t.y() == sigma_t.z()
+    }
+
+    /// Per-channel weight of travelling `distance` through the medium without a real collision
+    /// from `track`: each channel's transmittance over that of the average extinction.
+    pub fn tracking_weight(&self, distance: f64) -> Color {
+        if self.is_gray() {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+        let sigma_t = self.sigma_a + self.sigma_s;
+        let mean_t = mean(&sigma_t);
+        let weight = |sigma_t: f64| ((mean_t - sigma_t) * distance).exp();
+        Color::new(weight(sigma_t.x()), weight(sigma_t.y()), weight(sigma_t.z()))
+    }
+}
+
+/// Transmittance of a single channel, treating empty channels as fully clear even over infinite distance.
+#[inline]
+fn channel_transmittance(sigma_t: f64, distance: f64) -> f64 {
+    if sigma_t > 0.0 { (-sigma_t * distance).exp() } else { 1.0 }
+}
+
+/// Mean of a color's channels.
+#[inline]
+fn mean(c: &Color) -> f64 {
+    (c.x() + c.y() + c.z()) / 3.0
+}
+
+/// Channels relative to their mean, exactly white for gray coefficients.
+#[inline]
+fn relative(c: &Color) -> Color {
+    if c.x() == c.y() && c.y() == c.z() { Color::new(1.0, 1.0, 1.0) } else { *c / mean(c) }
+}
+
+// ----- Medium Stack -----
+
+/// The nested volumes a ray is inside, for interior media and nested dielectrics.
+/// Volumes have priorities: where they overlap, the one with the highest priority wins, and
+/// surfaces of lower-priority volumes inside it are ignored (e.g. liquid overlapping a glass wall).
+#[derive(Clone, Default)]
+pub struct MediumStack {
+    ambient: Option<Arc<Medium>>, // Medium outside every volume, e.g. fog filling the scene
+    entries: Vec<MediumEntry>,
+}
+
+#[derive(Clone)]
+struct MediumEntry {
+    boundary: Arc<Material>, // Identifies the volume by its boundary material
+    medium: Option<Arc<Medium>>,
+    priority: u32,
+    ior: f64,
+    refracts: bool, // Whether the boundary bends light, hiding lights outside from direct sampling
+}
+
+impl MediumStack {
+    /// Constructor for a ray starting outside every volume, in the given ambient medium.
+    pub fn new(ambient: Option<Arc<Medium>>) -> Self {
+        Self { ambient, entries: Vec::new() }
+    }
+
+    /// The medium the ray is currently travelling through, if any.
+    pub fn medium(&self) -> Option<&Arc<Medium>> {
+        match self.top(None) {
+            Some(entry) => entry.medium.as_ref(),
+            None => self.ambient.as_ref(),
+        }
+    }
+
+    /// Whether a surface of the volume with this boundary is a real interface, i.e. no other
+    /// volume the ray is inside has a higher priority.
+    pub fn is_true_boundary(&self, boundary: &Arc<Material>, priority: u32) -> bool {
+        self.top(Some(boundary)).is_none_or(|entry| entry.priority <= priority)
+    }
+
+    /// Refractive index on the far side of a volume's surface, i.e. of the volume that contains it.
+    pub fn outside_ior(&self, boundary: Option<&Arc<Material>>) -> f64 {
+        self.top(boundary).map_or(1.0, |entry| entry.ior)
+    }
+
+    /// Whether lights can be sampled directly from where the ray is, i.e. the volume it is in is not
+    /// sealed by a refracting surface. Rays aimed at a light from inside glass or skin are bent away
+    /// from it, so sampling lights there only adds noise.
+    pub fn lights_reachable(&self) -> bool {
+        self.top(None).is_none_or(|entry| !entry.refracts)
+    }
+
+    /// The stack after entering a volume, with the refractive index of its surface or None if it
+    /// does not refract, in which case the volume keeps the index of its surroundings.
+    pub fn entered(&self, boundary: &Arc<Material>, medium: Option<Arc<Medium>>, priority: u32, ior: Option<f64>) -> Self {
+        let mut next = self.clone();
+        next.entries.push(MediumEntry {
+            boundary: Arc::clone(boundary),
+            medium,
+            priority,
+            ior: ior.unwrap_or_else(|| self.outside_ior(Some(boundary))),
+            refracts: ior.is_some(),
+        });
+        next
+    }
+
+    /// The stack after leaving a volume.
+    pub fn exited(&self, boundary: &Arc<Material>) -> Self {
+        let mut next = self.clone();
+        if let Some(index) = next.entries.iter().rposition(|entry| Arc::ptr_eq(&entry.boundary, boundary)) {
+            next.entries.remove(index);
+        }
+        next
+    }
+
+    /// The highest-priority volume, latest entered on ties, ignoring the given one.
+    fn top(&self, ignore: Option<&Arc<Material>>) -> Option<&MediumEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| ignore.is_none_or(|boundary| !Arc::ptr_eq(&entry.boundary, boundary)))
+            .fold(None, |best: Option<&MediumEntry>, entry| match best {
+                Some(best) if best.priority > entry.priority => Some(best),
+                _ => Some(entry),
+            })
+    }
+}
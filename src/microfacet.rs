@@ -0,0 +1,143 @@
+use std::f64::consts::PI;
+
+use crate::onb::ONB;
+use crate::prelude::*;
+
+// Shared microfacet distribution, masking and Fresnel helpers used by the glossy materials.
+// All cosines are taken relative to the shading normal and are expected to be non-negative.
+
+/// Minimum GGX/GTR alpha, keeps near-mirror lobes numerically stable.
+pub const MIN_ALPHA: f64 = 1e-3;
+
+/// Convert a perceptual roughness in [0,1] to a GGX alpha (alpha = roughness^2).
+#[inline]
+pub fn roughness_to_alpha(roughness: f64) -> f64 {
+    let r = roughness.clamp(0.0, 1.0);
+    (r * r).max(MIN_ALPHA)
+}
+
+/// Schlick's Fresnel weight (1 - cos)^5.
+#[inline]
+pub fn schlick_weight(cosine: f64) -> f64 {
+    let m = (1.0 - cosine).clamp(0.0, 1.0);
+    let m2 = m * m;
+    m2 * m2 * m
+}
+
+/// Schlick's approximation for a colored normal-incidence reflectance `f0`.
+#[inline]
+pub fn schlick_fresnel(f0: Color, cosine: f64) -> Color {
+    f0 + schlick_weight(cosine) * (Color::new(1.0, 1.0, 1.0) - f0)
+}
+
+/// Exact unpolarized Fresnel reflectance for a dielectric interface.
+/// `eta` is the ratio of the refractive index on the incident side over the transmitted side.
+/// Returns 1.0 on total internal reflection.
+#[inline]
+pub fn fresnel_dielectric(cos_i: f64, eta: f64) -> f64 {
+    let cos_i = cos_i.clamp(0.0, 1.0);
+    let sin_t2 = eta * eta * (1.0 - cos_i * cos_i);
+    if sin_t2 >= 1.0 {
+        return 1.0;
+    }
+    let cos_t = (1.0 - sin_t2).sqrt();
+    let r_parl = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    let r_perp = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    0.5 * (r_parl * r_parl + r_perp * r_perp)
+}
+
+/// GGX (Trowbridge-Reitz) normal distribution D(h).
+#[inline]
+pub fn ggx_d(n_dot_h: f64, alpha: f64) -> f64 {
+    if n_dot_h <= 0.0 {
+        return 0.0;
+    }
+    let a2 = alpha * alpha;
+    let t = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    a2 / (PI * t * t)
+}
+
+/// Smith masking term G1 for the GGX distribution.
+#[inline]
+pub fn smith_g1(n_dot_v: f64, alpha: f64) -> f64 {
+    if n_dot_v <= 0.0 {
+        return 0.0;
+    }
+    let a2 = alpha * alpha;
+    2.0 * n_dot_v / (n_dot_v + (a2 + (1.0 - a2) * n_dot_v * n_dot_v).sqrt())
+}
+
+/// Separable Smith masking-shadowing G(l, v) for the GGX distribution.
+#[inline]
+pub fn smith_g(n_dot_l: f64, n_dot_v: f64, alpha: f64) -> f64 {
+    smith_g1(n_dot_l, alpha) * smith_g1(n_dot_v, alpha)
+}
+
+/// Berry (GTR1) distribution, used for the clearcoat lobe.
+#[inline]
+pub fn gtr1_d(n_dot_h: f64, alpha: f64) -> f64 {
+    if n_dot_h <= 0.0 {
+        return 0.0;
+    }
+    if alpha >= 1.0 {
+        return 1.0 / PI;
+    }
+    let a2 = alpha * alpha;
+    let t = 1.0 + (a2 - 1.0) * n_dot_h * n_dot_h;
+    (a2 - 1.0) / (PI * a2.ln() * t)
+}
+
+/// Sample a GGX half vector visible from `wo`, proportional to G1(wo) * max(0, wo.h) * D(h) / cos(theta_o)
+/// (Heitz 2018). Reflections about it are weighted by G(wo, wi) / G1(wo) alone.
+#[inline]
+pub fn sample_ggx_visible_half(uvw: &ONB, wo: &Vec3, alpha: f64) -> Vec3 {
+    // Stretch the view direction to the configuration of a hemisphere of unit roughness
+    let local = Vec3::new(Vec3::dot(wo, &uvw.u()), Vec3::dot(wo, &uvw.v()), Vec3::dot(wo, &uvw.w()));
+    let vh = Vec3::unit_vector(&Vec3::new(alpha * local.x(), alpha * local.y(), local.z()));
+
+    // Sample the projected area of the hemisphere seen from the view direction
+    let len_sq = vh.x() * vh.x() + vh.y() * vh.y();
+    let t1 = if len_sq > 0.0 { Vec3::new(-vh.y(), vh.x(), 0.0) / len_sq.sqrt() } else { Vec3::new(1.0, 0.0, 0.0) };
+    let t2 = Vec3::cross(&vh, &t1);
+    let r = random_f64().sqrt();
+    let phi = 2.0 * PI * random_f64();
+    let p1 = r * phi.cos();
+    let s = 0.5 * (1.0 + vh.z());
+    let p2 = (1.0 - s) * (1.0 - p1 * p1).max(0.0).sqrt() + s * r * phi.sin();
+    let nh = p1 * t1 + p2 * t2 + (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt() * vh;
+
+    // Unstretch back to the microfacet normal
+    uvw.transform(&Vec3::unit_vector(&Vec3::new(alpha * nh.x(), alpha * nh.y(), nh.z().max(0.0))))
+}
+
+/// Sample a GGX half vector proportional to D(h) * cos(theta_h) around the basis normal.
+#[inline]
+pub fn sample_ggx_half(uvw: &ONB, alpha: f64) -> Vec3 {
+    let r1 = random_f64();
+    let r2 = random_f64();
+    let phi = 2.0 * PI * r1;
+    let cos_theta = ((1.0 - r2) / (1.0 + (alpha * alpha - 1.0) * r2)).sqrt();
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    uvw.transform(&Vec3::new(phi.cos() * sin_theta, phi.sin() * sin_theta, cos_theta))
+}
+
+/// Sample a GTR1 half vector proportional to D(h) * cos(theta_h) around the basis normal.
+#[inline]
+pub fn sample_gtr1_half(uvw: &ONB, alpha: f64) -> Vec3 {
+    let r1 = random_f64();
+    let r2 = random_f64();
+    let phi = 2.0 * PI * r1;
+    let a2 = alpha * alpha;
+    let cos_theta = ((1.0 - a2.powf(1.0 - r2)) / (1.0 - a2)).max(0.0).sqrt();
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    uvw.transform(&Vec3::new(phi.cos() * sin_theta, phi.sin() * sin_theta, cos_theta))
+}
+
+/// Solid-angle PDF of a reflected direction whose half vector was sampled with density D(h) * cos(theta_h).
+#[inline]
+pub fn reflection_pdf(d: f64, n_dot_h: f64, v_dot_h: f64) -> f64 {
+    if v_dot_h <= 0.0 {
+        return 0.0;
+    }
+    d * n_dot_h / (4.0 * v_dot_h)
+}
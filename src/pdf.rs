@@ -1,8 +1,11 @@
 use std::f64::consts::PI;
 use std::sync::Arc;
 
+use crate::environment::EnvironmentMap;
 use crate::hittable::Hittable;
+use crate::microfacet;
 use crate::onb::ONB;
+use crate::phase;
 use crate::vec3::{Point3, Vec3};
 use crate::prelude::*;
 
@@ -15,6 +18,9 @@ pub enum PDF {
     Cosine(CosinePDF),
     Hittable(HittablePDF),
     Mixture(MixturePDF),
+    Principled(PrincipledPDF),
+    Environment(EnvironmentPDF),
+    HenyeyGreenstein(HenyeyGreensteinPDF),
 }
 
 impl PDF {
@@ -26,6 +32,9 @@ impl PDF {
             PDF::Cosine(pdf) => pdf.value(direction),
             PDF::Hittable(pdf) => pdf.value(direction),
             PDF::Mixture(pdf) => pdf.value(direction),
+            PDF::Principled(pdf) => pdf.value(direction),
+            PDF::Environment(pdf) => pdf.value(direction),
+            PDF::HenyeyGreenstein(pdf) => pdf.value(direction),
         }
     }
 
@@ -37,6 +46,9 @@ impl PDF {
             PDF::Cosine(pdf) => pdf.generate(),
             PDF::Hittable(pdf) => pdf.generate(),
             PDF::Mixture(pdf) => pdf.generate(),
+            PDF::Principled(pdf) => pdf.generate(),
+            PDF::Environment(pdf) => pdf.generate(),
+            PDF::HenyeyGreenstein(pdf) => pdf.generate(),
         }
     }
 
@@ -58,6 +70,18 @@ impl PDF {
     pub fn mixture(pdf1: Arc<Self>, pdf2: Arc<Self>) -> Arc<Self> {
         Arc::new(Self::Mixture(MixturePDF::new(pdf1, pdf2)))
     }
+    /// Create an Arc<PDF> for a PrincipledPdf around the given normal, viewed from direction `wo`.
+    pub fn principled(normal: &Vec3, wo: &Vec3, alpha: f64, clearcoat_alpha: f64, lobe_weights: [f64; 3]) -> Arc<Self> {
+        Arc::new(Self::Principled(PrincipledPDF::new(normal, wo, alpha, clearcoat_alpha, lobe_weights)))
+    }
+    /// Create an Arc<PDF> for an EnvironmentPdf sampling directions by the environment's luminance.
+    pub fn environment(environment: Arc<EnvironmentMap>) -> Arc<Self> {
+        Arc::new(Self::Environment(EnvironmentPDF::new(environment)))
+    }
+    /// Create an Arc<PDF> for a (double) Henyey-Greenstein phase function around the incoming direction.
+    pub fn henyey_greenstein(direction: &Vec3, g: [f64; 2], weight: f64) -> Arc<Self> {
+        Arc::new(Self::HenyeyGreenstein(HenyeyGreensteinPDF::new(direction, g, weight)))
+    }
 }
 
 // ----- Sphere PDF -----
@@ -138,6 +162,63 @@ impl HittablePDF {
     }
 }
 
+// ----- Environment PDF -----
+#[derive(Clone)]
+pub struct EnvironmentPDF {
+    environment: Arc<EnvironmentMap>,
+}
+
+impl EnvironmentPDF {
+    /// Creates a new EnvironmentPdf instance for the given environment map.
+    pub fn new(environment: Arc<EnvironmentMap>) -> Self {
+        Self { environment }
+    }
+
+    /// Evaluates the PDF value for a given direction from the environment's luminance distribution.
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        self.environment.pdf(direction)
+    }
+
+    /// Generates a random direction towards the bright parts of the environment.
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        self.environment.sample()
+    }
+}
+
+// ----- Henyey-Greenstein PDF -----
+
+/// A blend of two Henyey-Greenstein lobes around the direction light was travelling.
+#[derive(Clone)]
+pub struct HenyeyGreensteinPDF {
+    uvw: ONB,    // w is the incoming propagation direction
+    g: [f64; 2], // Asymmetry of each lobe
+    weight: f64, // Probability of the first lobe
+}
+
+impl HenyeyGreensteinPDF {
+    /// Creates a new HenyeyGreensteinPdf instance for light travelling along `direction`.
+    pub fn new(direction: &Vec3, g: [f64; 2], weight: f64) -> Self {
+        Self { uvw: ONB::new(direction), g, weight }
+    }
+
+    /// Evaluates the PDF value for a given direction as the weighted sum of the lobes.
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        let cos_theta = Vec3::dot(&Vec3::unit_vector(direction), &self.uvw.w());
+        self.weight * phase::henyey_greenstein(cos_theta, self.g[0])
+            + (1.0 - self.weight) * phase::henyey_greenstein(cos_theta, self.g[1])
+    }
+
+    /// Generates a random direction by picking a lobe according to its weight and sampling it.
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        let g = if random_f64() < self.weight { self.g[0] } else { self.g[1] };
+        phase::sample_henyey_greenstein(&self.uvw, g)
+    }
+}
+
 // ----- Mixture PDF -----
 #[derive(Clone)]
 pub struct MixturePDF {
@@ -165,4 +246,69 @@ impl MixturePDF {
             self.pdfs[1].generate()
         }
     }
-}
\ No newline at end of file
+}
+
+// ----- Principled PDF -----
+
+/// Lobe mixture PDF for the principled material: cosine-weighted diffuse,
+/// GGX specular reflection and GTR1 clearcoat reflection.
+#[derive(Clone)]
+pub struct PrincipledPDF {
+    uvw: ONB,
+    wo: Vec3,               // Unit direction towards the viewer
+    alpha: f64,             // GGX alpha of the specular lobe
+    clearcoat_alpha: f64,   // GTR1 alpha of the clearcoat lobe
+    lobe_weights: [f64; 3], // Selection probabilities [diffuse, specular, clearcoat], sum to 1
+}
+
+impl PrincipledPDF {
+    /// Creates a new PrincipledPdf instance. Lobe weights are normalized here.
+    pub fn new(normal: &Vec3, wo: &Vec3, alpha: f64, clearcoat_alpha: f64, lobe_weights: [f64; 3]) -> Self {
+        let total: f64 = lobe_weights.iter().sum();
+        let lobe_weights = if total > 0.0 { lobe_weights.map(|w| w / total) } else { [1.0, 0.0, 0.0] };
+        Self {
+            uvw: ONB::new(normal),
+            wo: Vec3::unit_vector(wo),
+            alpha,
+            clearcoat_alpha,
+            lobe_weights,
+        }
+    }
+
+    /// Evaluates the PDF value for a given direction as the weighted sum of the lobe PDFs.
+    #[inline]
+    fn value(&self, direction: &Vec3) -> f64 {
+        let wi = Vec3::unit_vector(direction);
+        let n = self.uvw.w();
+        let cos_theta = Vec3::dot(&wi, &n);
+        if cos_theta <= 0.0 {
+            return 0.0; // All lobes are reflective
+        }
+
+        let h = Vec3::unit_vector(&(wi + self.wo));
+        let n_dot_h = Vec3::dot(&n, &h);
+        let v_dot_h = Vec3::dot(&self.wo, &h);
+
+        let diffuse = cos_theta / PI;
+        let specular = microfacet::reflection_pdf(microfacet::ggx_d(n_dot_h, self.alpha), n_dot_h, v_dot_h);
+        let clearcoat = microfacet::reflection_pdf(microfacet::gtr1_d(n_dot_h, self.clearcoat_alpha), n_dot_h, v_dot_h);
+
+        self.lobe_weights[0] * diffuse + self.lobe_weights[1] * specular + self.lobe_weights[2] * clearcoat
+    }
+
+    /// Generates a random direction by picking a lobe according to its weight and sampling it.
+    #[inline]
+    fn generate(&self) -> Vec3 {
+        let r = random_f64();
+        if r < self.lobe_weights[0] {
+            return self.uvw.transform(&Vec3::random_cosine_direction());
+        }
+
+        let h = if r < self.lobe_weights[0] + self.lobe_weights[1] {
+            microfacet::sample_ggx_half(&self.uvw, self.alpha)
+        } else {
+            microfacet::sample_gtr1_half(&self.uvw, self.clearcoat_alpha)
+        };
+        Vec3::reflect(&-self.wo, &h)
+    }
+}
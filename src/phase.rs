@@ -0,0 +1,34 @@
+use std::f64::consts::PI;
+
+use crate::onb::ONB;
+use crate::prelude::*;
+
+// Phase functions for participating media. Cosines are between the direction light travels
+// before scattering and the direction after it, so a positive asymmetry `g` scatters forward.
+
+/// Largest asymmetry magnitude, keeps strongly peaked lobes numerically stable.
+pub const MAX_ASYMMETRY: f64 = 0.99;
+
+/// Henyey-Greenstein phase function, normalized over the sphere. `g` in (-1,1) is the mean
+/// cosine of the scattering angle: 0 is isotropic, positive forward and negative backward.
+#[inline]
+pub fn henyey_greenstein(cos_theta: f64, g: f64) -> f64 {
+    let denom = 1.0 + g * g - 2.0 * g * cos_theta;
+    (1.0 - g * g) / (4.0 * PI * denom * denom.sqrt())
+}
+
+/// Sample a scattered direction from the Henyey-Greenstein lobe around the propagation
+/// direction `uvw.w()`, inverting its CDF in the cosine.
+#[inline]
+pub fn sample_henyey_greenstein(uvw: &ONB, g: f64) -> Vec3 {
+    let cos_theta = if g.abs() < 1e-3 {
+        1.0 - 2.0 * random_f64()
+    } else {
+        let s = (1.0 - g * g) / (1.0 - g + 2.0 * g * random_f64());
+        ((1.0 + g * g - s * s) / (2.0 * g)).clamp(-1.0, 1.0)
+    };
+
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = 2.0 * PI * random_f64();
+    uvw.transform(&Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta))
+}
@@ -0,0 +1,91 @@
+use std::f64::consts::PI;
+
+use crate::color::Color;
+
+// Physically based light units. Scene distances are taken to be meters and renderer radiance is
+// radiometric, W/(sr*m^2), with the luminance of an RGB radiance standing in for its luminous weight.
+
+/// Maximum luminous efficacy of radiation, used to convert between lumens and watts.
+pub const LUMENS_PER_WATT: f64 = 683.0;
+
+/// The power or brightness of an area light, converted to radiance by the light's emitting area.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LightPower {
+    Watts(f64),  // Total radiant flux leaving the emitter
+    Lumens(f64), // Total luminous flux leaving the emitter
+    Nits(f64),   // Luminance (cd/m^2) of the emitting surface, independent of area
+}
+
+impl LightPower {
+    /// Radiance (luminance of the emitted RGB) of a Lambertian emitter with the given emitting area.
+    /// A Lambertian surface of radiance L and area A emits a flux of `pi * L * A`.
+    pub fn radiance(&self, area: f64) -> f64 {
+        match *self {
+            LightPower::Nits(nits) => nits / LUMENS_PER_WATT,
+            _ => self.watts().unwrap_or(0.0) / (PI * area.max(f64::MIN_POSITIVE)),
+        }
+    }
+
+    /// Total radiant flux in watts, or None for `Nits`, which describes a surface rather than a total power.
+    pub fn watts(&self) -> Option<f64> {
+        match *self {
+            LightPower::Watts(watts) => Some(watts),
+            LightPower::Lumens(lumens) => Some(lumens / LUMENS_PER_WATT),
+            LightPower::Nits(_) => None,
+        }
+    }
+}
+
+/// Linear sRGB color of a blackbody radiator at the given temperature in Kelvin, normalized to unit luminance.
+pub fn blackbody(kelvin: f64) -> Color {
+    let kelvin = kelvin.max(1.0);
+
+    // Integrate Planck's law against the CIE 1931 matching functions over the visible range
+    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+    let mut lambda = 380.0;
+    while lambda <= 780.0 {
+        let radiance = planck(lambda, kelvin);
+        let (xb, yb, zb) = cie_xyz(lambda);
+        x += radiance * xb;
+        y += radiance * yb;
+        z += radiance * zb;
+        lambda += 5.0;
+    }
+    if y <= 0.0 {
+        return Color::zero();
+    }
+
+    let color = xyz_to_linear_srgb(x / y, 1.0, z / y);
+    color / color.luminance()
+}
+
+/// CIE XYZ (D65 white) to linear sRGB, clamping out-of-gamut components.
+pub fn xyz_to_linear_srgb(x: f64, y: f64, z: f64) -> Color {
+    Color::new(
+        (3.2406 * x - 1.5372 * y - 0.4986 * z).max(0.0),
+        (-0.9689 * x + 1.8758 * y + 0.0415 * z).max(0.0),
+        (0.0557 * x - 0.2040 * y + 1.0570 * z).max(0.0),
+    )
+}
+
+/// Planck's spectral radiance at wavelength `lambda_nm`, up to a constant factor.
+#[inline]
+fn planck(lambda_nm: f64, kelvin: f64) -> f64 {
+    const C2: f64 = 1.4387769e7; // Second radiation constant h*c/k in nm*K
+    let l = lambda_nm * 1e-3; // In micrometers to keep the magnitudes reasonable
+    1.0 / (l.powi(5) * ((C2 / (lambda_nm * kelvin)).exp() - 1.0))
+}
+
+/// Multi-lobe Gaussian fit of the CIE 1931 2-degree color matching functions (Wyman, Sloan and Shirley 2013).
+#[inline]
+fn cie_xyz(lambda_nm: f64) -> (f64, f64, f64) {
+    let g = |l: f64, mu: f64, s1: f64, s2: f64| {
+        let t = (l - mu) / if l < mu { s1 } else { s2 };
+        (-0.5 * t * t).exp()
+    };
+    let l = lambda_nm;
+    let x = 1.056 * g(l, 599.8, 37.9, 31.0) + 0.362 * g(l, 442.0, 16.0, 26.7) - 0.065 * g(l, 501.1, 20.4, 26.2);
+    let y = 0.821 * g(l, 568.8, 46.9, 40.5) + 0.286 * g(l, 530.9, 16.3, 31.1);
+    let z = 1.217 * g(l, 437.0, 11.8, 36.0) + 0.681 * g(l, 459.0, 26.0, 13.8);
+    (x, y, z)
+}
@@ -0,0 +1,91 @@
+use crate::photometry::LUMENS_PER_WATT;
+
+// Camera body and exposure settings as on a real camera. Scene radiance is radiometric (see
+// `photometry`), so exposure maps luminance in nits to the display's [0,1] range the way a
+// sensor of the given sensitivity would, matching photographs taken with the same settings.
+
+/// Ratio of the luminance that saturates a sensor to the exposure's reference luminance, from the
+/// saturation-based ISO speed definition (78 / (100 * 0.65)).
+const SATURATION_FACTOR: f64 = 1.2;
+
+/// Physical camera settings from which the field of view, depth of field, motion blur and
+/// brightness of the image follow.
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicalCamera {
+    pub focal_length: f64,          // Lens focal length in millimeters
+    pub sensor_width: f64,          // Sensor width in millimeters, cropped in height to the image aspect ratio
+    pub f_stop: f64,                // Relative aperture N, the focal length over the aperture diameter
+    pub shutter_speed: f64,         // Exposure time in seconds of scene time
+    pub iso: f64,                   // Sensor sensitivity
+    pub exposure_compensation: f64, // Extra exposure in stops, positive for brighter
+    pub units_per_mm: f64,          // World units per millimeter, 0.001 for scenes modeled in meters
+}
+
+impl PhysicalCamera {
+    /// Constructor for a full-frame (36mm wide) camera in a scene modeled in meters.
+    pub fn new(focal_length: f64, f_stop: f64, shutter_speed: f64, iso: f64) -> Self {
+        assert!(focal_length > 0.0 && f_stop > 0.0 && shutter_speed >= 0.0 && iso > 0.0,
+            "focal length, f-stop and ISO must be positive and the shutter speed non-negative");
+        Self {
+            focal_length,
+            sensor_width: 36.0,
+            f_stop,
+            shutter_speed,
+            iso,
+            exposure_compensation: 0.0,
+            units_per_mm: 0.001,
+        }
+    }
+
+    /// Set the sensor width in millimeters, e.g. 23.5 for APS-C or 17.3 for Micro Four Thirds.
+    pub fn with_sensor_width(mut self, sensor_width: f64) -> Self {
+        self.sensor_width = sensor_width;
+        self
+    }
+
+    /// Brighten or darken the exposure by a number of stops.
+    pub fn with_exposure_compensation(mut self, stops: f64) -> Self {
+        self.exposure_compensation = stops;
+        self
+    }
+
+    /// Set how many world units make a millimeter, e.g. 0.1 for a scene modeled in centimeters.
+    pub fn with_units_per_mm(mut self, units_per_mm: f64) -> Self {
+        self.units_per_mm = units_per_mm;
+        self
+    }
+
+    /// Exposure value at ISO 100 of these settings, e.g. about 15 for sunny daylight and 5 for a lit room.
+    pub fn ev100(&self) -> f64 {
+        (self.f_stop * self.f_stop / self.shutter_speed.max(f64::MIN_POSITIVE) * 100.0 / self.iso).log2()
+            - self.exposure_compensation
+    }
+
+    /// Factor taking scene radiance to display values, saturating at the luminance the sensor clips at.
+    pub fn exposure_scale(&self) -> f64 {
+        let max_luminance = SATURATION_FACTOR * 2f64.powf(self.ev100());
+        LUMENS_PER_WATT / max_luminance
+    }
+
+    /// Vertical field of view in degrees when focused at a distance in world units. The lens moves
+    /// away from the sensor to focus closer, narrowing the view slightly (focus breathing).
+    pub fn v_fov(&self, focus_dist: f64, aspect_ratio: f64) -> f64 {
+        let image_distance = self.image_distance(focus_dist);
+        let sensor_height = self.sensor_width / aspect_ratio;
+        2.0 * (0.5 * sensor_height / image_distance).atan().to_degrees()
+    }
+
+    /// Angle the aperture subtends from the plane of focus, in degrees, for the camera's thin lens.
+    pub fn aperture_angle(&self, focus_dist: f64) -> f64 {
+        let aperture_radius = 0.5 * self.focal_length / self.f_stop * self.units_per_mm;
+        2.0 * (aperture_radius / focus_dist).atan().to_degrees()
+    }
+
+    /// Lens to sensor distance in millimeters when focused at a distance in world units, from the
+    /// thin lens equation 1/f = 1/object + 1/image.
+    fn image_distance(&self, focus_dist: f64) -> f64 {
+        let object_distance = focus_dist / self.units_per_mm;
+        assert!(object_distance > self.focal_length, "a lens cannot focus closer than its focal length");
+        self.focal_length * object_distance / (object_distance - self.focal_length)
+    }
+}
@@ -8,6 +8,7 @@ use crate::vec3::Point3;
 pub struct PlyMeshData {
     pub positions: Vec<Point3>,
     pub indices: Vec<[u32; 3]>,
+    pub uvs: Option<Vec<(f64, f64)>>, // Per-vertex texture coordinates, if the file has them
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -148,6 +149,7 @@ fn parse_ascii_body<R: BufRead>(reader: &mut R, header: &Header) -> io::Result<P
     // Locate the vertex and face elements; ignore any others
     let mut positions: Vec<Point3> = Vec::new();
     let mut indices: Vec<[u32; 3]> = Vec::new();
+    let mut uvs: Option<Vec<(f64, f64)>> = None;
 
     // Buffer reused across lines
     let mut line = String::new();
@@ -157,7 +159,9 @@ fn parse_ascii_body<R: BufRead>(reader: &mut R, header: &Header) -> io::Result<P
             "vertex" => {
                 positions.reserve_exact(element.count);
                 let (x_pos, y_pos, z_pos) = locate_xyz(&element.properties)?;
+                let uv_pos = locate_uv(&element.properties);
                 let prop_count = element.properties.len();
+                let mut vertex_uvs: Vec<(f64, f64)> = Vec::with_capacity(if uv_pos.is_some() { element.count } else { 0 });
 
                 for _ in 0..element.count {
                     line.clear();
@@ -166,19 +170,26 @@ fn parse_ascii_body<R: BufRead>(reader: &mut R, header: &Header) -> io::Result<P
                     let mut fields = line.split_whitespace();
                     let mut vals = [0.0f64; 3];
                     let mut have = [false; 3];
+                    let mut uv = (0.0f64, 0.0f64);
                     for i in 0..prop_count {
                         let tok = fields.next().ok_or_else(||
                             Error::new(ErrorKind::InvalidData, "short vertex line"))?;
                         if i == x_pos { vals[0] = parse_f64(tok)?; have[0] = true; }
                         else if i == y_pos { vals[1] = parse_f64(tok)?; have[1] = true; }
                         else if i == z_pos { vals[2] = parse_f64(tok)?; have[2] = true; }
+                        else if let Some((u_pos, v_pos)) = uv_pos {
+                            if i == u_pos { uv.0 = parse_f64(tok)?; }
+                            else if i == v_pos { uv.1 = parse_f64(tok)?; }
+                        }
                         // else: skip token (e.g. r/g/b/normal)
                     }
                     if !have[0] || !have[1] || !have[2] {
                         return Err(Error::new(ErrorKind::InvalidData, "missing x/y/z on vertex"));
                     }
                     positions.push(Point3::new(vals[0], vals[1], vals[2]));
+                    if uv_pos.is_some() { vertex_uvs.push(uv); }
                 }
+                if uv_pos.is_some() { uvs = Some(vertex_uvs); }
             }
             "face" => {
                 indices.reserve(element.count); // can grow if there is fan triangulation
@@ -221,7 +232,7 @@ fn parse_ascii_body<R: BufRead>(reader: &mut R, header: &Header) -> io::Result<P
 
     println!("Loaded mesh: {} vertices, {} faces", positions.len(), indices.len());
 
-    Ok(PlyMeshData { positions, indices })
+    Ok(PlyMeshData { positions, indices, uvs })
 }
 
 fn locate_xyz(props: &[Property]) -> io::Result<(usize, usize, usize)> {
@@ -242,6 +253,15 @@ fn locate_xyz(props: &[Property]) -> io::Result<(usize, usize, usize)> {
     }
 }
 
+/// Locate the texture coordinate properties, which exporters name u/v, s/t or texture_u/texture_v.
+fn locate_uv(props: &[Property]) -> Option<(usize, usize)> {
+    let position = |names: &[&str]| props.iter().position(|p| matches!(p,
+        Property::Scalar { name, .. } if names.contains(&name.as_str())));
+    let u = position(&["u", "s", "texture_u", "texture_s"])?;
+    let v = position(&["v", "t", "texture_v", "texture_t"])?;
+    Some((u, v))
+}
+
 #[inline]
 fn parse_f64(s: &str) -> io::Result<f64> {
     s.parse().map_err(|_|
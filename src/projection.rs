@@ -0,0 +1,104 @@
+use crate::prelude::*;
+
+use std::f64::consts::PI;
+
+// ----- Projection -----
+
+/// How the camera maps the image onto directions in the scene.
+#[derive(Debug, Clone, Copy)]
+pub enum Projection {
+    /// Pinhole or thin-lens perspective, with the camera's vertical field of view.
+    Perspective,
+    /// Parallel rays, with the viewport height in world units, for technical and isometric views.
+    Orthographic { view_height: f64 },
+    /// Circular fisheye filling the shorter image side, with the field of view across the circle in degrees.
+    Fisheye { fov: f64, mapping: FisheyeMapping },
+    /// Full 360 x 180 degree panorama in latitude-longitude layout, for 2:1 images.
+    Equirectangular,
+}
+
+/// How a fisheye lens maps the angle from its axis to the distance from the image center.
+#[derive(Debug, Clone, Copy)]
+pub enum FisheyeMapping {
+    Equidistant, // Distance proportional to angle, r = f θ
+    Equisolid,   // Equal areas for equal solid angles, r = 2 f sin(θ/2)
+}
+
+impl Projection {
+    /// Constructor for an orthographic projection showing `view_height` world units vertically.
+    pub fn orthographic(view_height: f64) -> Self {
+        Projection::Orthographic { view_height }
+    }
+
+    /// Constructor for an equidistant fisheye with a field of view in degrees, up to 360.
+    pub fn fisheye(fov: f64) -> Self {
+        Projection::Fisheye { fov: fov.clamp(EPSILON, 360.0), mapping: FisheyeMapping::Equidistant }
+    }
+
+    /// Constructor for an equisolid-angle fisheye with a field of view in degrees, up to 360.
+    pub fn fisheye_equisolid(fov: f64) -> Self {
+        Projection::Fisheye { fov: fov.clamp(EPSILON, 360.0), mapping: FisheyeMapping::Equisolid }
+    }
+
+    /// Direction in camera space (x right, y up, looking down -z) through a point on the image,
+    /// given in [0,1]^2 from the top-left corner, for the projections that map the image to angles.
+    /// None outside a fisheye's image circle, and for the planar projections.
+    pub fn direction(&self, s: f64, t: f64, width: u32, height: u32) -> Option<Vec3> {
+        match *self {
+            Projection::Fisheye { fov, mapping } => {
+                // Image coordinates scaled so the circle has radius 1
+                let short = width.min(height) as f64;
+                let x = (2.0 * s - 1.0) * width as f64 / short;
+                let y = (1.0 - 2.0 * t) * height as f64 / short;
+                let r = (x * x + y * y).sqrt();
+                if r > 1.0 { return None; }
+
+                let half_fov = 0.5 * fov.to_radians();
+                let theta = match mapping {
+                    FisheyeMapping::Equidistant => r * half_fov,
+                    FisheyeMapping::Equisolid => 2.0 * (r * (0.5 * half_fov).sin()).clamp(-1.0, 1.0).asin(),
+                };
+                let phi = y.atan2(x);
+                Some(Vec3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), -theta.cos()))
+            }
+            Projection::Equirectangular => {
+                let longitude = (2.0 * s - 1.0) * PI;
+                let latitude = (0.5 - t) * PI;
+                Some(Vec3::new(
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin(),
+                    -latitude.cos() * longitude.cos(),
+                ))
+            }
+            Projection::Perspective | Projection::Orthographic { .. } => None,
+        }
+    }
+}
+
+// ----- Stereo -----
+
+/// Stereo output for VR review: both eyes rendered into one image.
+#[derive(Debug, Clone, Copy)]
+pub struct Stereo {
+    pub eye_separation: f64,    // Distance between the eyes in world units, about 0.064 for a human at meter scale
+    pub layout: StereoLayout,
+}
+
+/// Where each eye goes in a stereo image.
+#[derive(Debug, Clone, Copy)]
+pub enum StereoLayout {
+    SideBySide, // Left eye on the left, image twice as wide
+    TopBottom,  // Left eye on top, image twice as tall (over-under, usual for 360 video)
+}
+
+impl Stereo {
+    /// Constructor for side-by-side stereo with the given eye separation.
+    pub fn side_by_side(eye_separation: f64) -> Self {
+        Self { eye_separation, layout: StereoLayout::SideBySide }
+    }
+
+    /// Constructor for top-bottom stereo with the given eye separation.
+    pub fn top_bottom(eye_separation: f64) -> Self {
+        Self { eye_separation, layout: StereoLayout::TopBottom }
+    }
+}
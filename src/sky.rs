@@ -0,0 +1,156 @@
+use std::f64::consts::PI;
+
+use crate::environment::EnvironmentMap;
+use crate::photometry::{self, LUMENS_PER_WATT};
+use crate::prelude::*;
+
+// Preetham, Shirley and Smits (1999) analytic daylight model. Luminance comes out in cd/m^2 and is
+// converted to renderer radiance with the same convention as `photometry`, so a clear midday sky
+// has a radiance around 10. Scale it with `EnvironmentMap::with_intensity` for display.
+
+/// Extraterrestrial solar illuminance in lux, before atmospheric attenuation.
+const SOLAR_ILLUMINANCE: f64 = 128_000.0;
+
+/// A procedural clear sky with a sun disk, driven by sun position and atmospheric turbidity.
+#[derive(Clone)]
+pub struct Sky {
+    sun_direction: Vec3, // Unit direction towards the sun
+    turbidity: f64,      // Haziness: 2 is very clear, 3 clear, 6 hazy, 10 very hazy
+    ground_albedo: Color,
+    sun_radius: f64,     // Angular radius of the sun disk in radians
+}
+
+impl Sky {
+    /// Constructor from the sun's elevation above the horizon and its azimuth, in degrees.
+    /// Azimuth 0 puts the sun towards -z, increasing towards +x.
+    pub fn new(sun_elevation: f64, sun_azimuth: f64) -> Self {
+        let elevation = sun_elevation.clamp(0.0, 90.0).to_radians();
+        let azimuth = sun_azimuth.to_radians();
+        Self {
+            sun_direction: Vec3::new(
+                azimuth.sin() * elevation.cos(),
+                elevation.sin(),
+                -azimuth.cos() * elevation.cos(),
+            ),
+            turbidity: 3.0,
+            ground_albedo: Color::new(0.3, 0.3, 0.3),
+            sun_radius: 0.2667_f64.to_radians(),
+        }
+    }
+
+    /// Set the atmospheric turbidity, clamped to the model's valid range [1.7, 10].
+    pub fn with_turbidity(mut self, turbidity: f64) -> Self {
+        self.turbidity = turbidity.clamp(1.7, 10.0);
+        self
+    }
+
+    /// Set the albedo of the ground below the horizon.
+    pub fn with_ground_albedo(mut self, albedo: Color) -> Self {
+        self.ground_albedo = albedo;
+        self
+    }
+
+    /// Set the angular radius of the sun disk in degrees. Larger suns give softer shadows.
+    pub fn with_sun_size(mut self, degrees: f64) -> Self {
+        self.sun_radius = degrees.clamp(0.01, 10.0).to_radians();
+        self
+    }
+
+    /// Unit direction towards the sun.
+    pub fn sun_direction(&self) -> Vec3 {
+        self.sun_direction
+    }
+
+    /// Bake the sky and sun into an importance-sampled environment of the given resolution.
+    /// A sun smaller than a texel is widened to one texel, keeping its total power.
+    pub fn to_environment(&self, width: u32, height: u32) -> EnvironmentMap {
+        let radius = self.sun_radius.max(PI / height.max(1) as f64);
+        let cos_radius = radius.cos();
+        let sun = self.sun_radiance() * (cone_solid_angle(self.sun_radius) / cone_solid_angle(radius));
+
+        EnvironmentMap::from_fn(width, height, |direction| {
+            let sky = self.radiance(direction);
+            if Vec3::dot(direction, &self.sun_direction) >= cos_radius { sky + sun } else { sky }
+        })
+    }
+
+    /// Sky radiance along a direction, excluding the sun disk.
+    pub fn radiance(&self, direction: &Vec3) -> Color {
+        let d = Vec3::unit_vector(direction);
+        if d.y() < 0.0 {
+            // Ground: reflect the sky just above the horizon
+            let horizon = Vec3::unit_vector(&Vec3::new(d.x(), 0.01, d.z()));
+            return self.ground_albedo * self.radiance(&horizon);
+        }
+
+        let t = self.turbidity;
+        let theta_s = self.sun_direction.y().clamp(0.0, 1.0).acos();
+        let theta = d.y().clamp(0.01, 1.0).acos();
+        let gamma = Vec3::dot(&d, &self.sun_direction).clamp(-1.0, 1.0).acos();
+
+        // Zenith luminance (kcd/m^2) and chromaticity
+        let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * theta_s);
+        let zenith_y = ((4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192).max(0.0);
+        let (t2, s1, s2, s3) = (t * t, theta_s, theta_s * theta_s, theta_s * theta_s * theta_s);
+        let zenith_x = t2 * (0.00166 * s3 - 0.00375 * s2 + 0.00209 * s1)
+            + t * (-0.02903 * s3 + 0.06377 * s2 - 0.03202 * s1 + 0.00394)
+            + (0.11693 * s3 - 0.21196 * s2 + 0.06052 * s1 + 0.25886);
+        let zenith_yc = t2 * (0.00275 * s3 - 0.00610 * s2 + 0.00317 * s1)
+            + t * (-0.04214 * s3 + 0.08970 * s2 - 0.04153 * s1 + 0.00516)
+            + (0.15346 * s3 - 0.26756 * s2 + 0.06670 * s1 + 0.26688);
+
+        // Perez distribution coefficients for luminance and the two chromaticities
+        let coeffs_y = [0.1787 * t - 1.4630, -0.3554 * t + 0.4275, -0.0227 * t + 5.3251, 0.1206 * t - 2.5771, -0.0670 * t + 0.3703];
+        let coeffs_x = [-0.0193 * t - 0.2592, -0.0665 * t + 0.0008, -0.0004 * t + 0.2125, -0.0641 * t - 0.8989, -0.0033 * t + 0.0452];
+        let coeffs_yc = [-0.0167 * t - 0.2608, -0.0950 * t + 0.0092, -0.0079 * t + 0.2102, -0.0441 * t - 1.6537, -0.0109 * t + 0.0529];
+
+        let relative = |c: &[f64; 5], zenith: f64| zenith * perez(c, theta, gamma) / perez(c, 0.0, theta_s);
+        let luminance = 1000.0 * relative(&coeffs_y, zenith_y); // cd/m^2
+        let x = relative(&coeffs_x, zenith_x);
+        let y = relative(&coeffs_yc, zenith_yc);
+        if y <= 0.0 {
+            return Color::zero();
+        }
+
+        // xyY -> XYZ -> linear sRGB, then to renderer radiance
+        let color = photometry::xyz_to_linear_srgb(x / y * luminance, luminance, (1.0 - x - y) / y * luminance);
+        color / LUMENS_PER_WATT
+    }
+
+    /// Radiance of the sun disk after attenuation through the atmosphere.
+    pub fn sun_radiance(&self) -> Color {
+        let cos_theta = self.sun_direction.y();
+        if cos_theta <= 0.0 {
+            return Color::zero();
+        }
+
+        // Relative optical air mass (Kasten and Young)
+        let elevation_deg = 90.0 - cos_theta.acos().to_degrees();
+        let air_mass = 1.0 / (cos_theta + 0.50572 * (elevation_deg + 6.07995).powf(-1.6364));
+
+        // Rayleigh and aerosol (Angstrom) optical depths at representative R, G, B wavelengths
+        let beta = 0.04608 * self.turbidity - 0.04586;
+        let transmittance = |lambda_um: f64| {
+            let rayleigh = 0.008735 * lambda_um.powf(-4.08);
+            let aerosol = beta * lambda_um.powf(-1.3);
+            (-air_mass * (rayleigh + aerosol)).exp()
+        };
+        let attenuation = Color::new(transmittance(0.61), transmittance(0.55), transmittance(0.465));
+
+        let radiance = SOLAR_ILLUMINANCE / (LUMENS_PER_WATT * cone_solid_angle(self.sun_radius));
+        radiance * photometry::blackbody(5778.0) * attenuation
+    }
+}
+
+/// Perez et al. all-weather sky luminance distribution.
+#[inline]
+fn perez(c: &[f64; 5], theta: f64, gamma: f64) -> f64 {
+    let cos_gamma = gamma.cos();
+    (1.0 + c[0] * (c[1] / theta.cos().max(0.01)).exp()) * (1.0 + c[2] * (c[3] * gamma).exp() + c[4] * cos_gamma * cos_gamma)
+}
+
+/// Solid angle of a cone with the given half-angle in radians.
+#[inline]
+fn cone_solid_angle(half_angle: f64) -> f64 {
+    2.0 * PI * (1.0 - half_angle.cos())
+}
@@ -26,12 +26,24 @@ impl Texture {
         }
     }
 
+    /// Sample the texture as a scalar parameter (mean of the color channels).
+    /// Used for material parameters such as roughness or metallic maps.
+    #[inline]
+    pub fn scalar_value(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        let c = self.value(u, v, p);
+        (c.x() + c.y() + c.z()) / 3.0
+    }
+
     // Convenience Arc constructors
 
     /// Create an Arc<Texture> solid color from a Color.
     pub fn solid(albedo: Color) -> Arc<Texture> {
         Arc::new(Texture::SolidColor(SolidColor::new(albedo)))
     }
+    /// Create an Arc<Texture> constant grayscale value, for scalar material parameters.
+    pub fn scalar(value: f64) -> Arc<Texture> {
+        Self::solid(Color::new(value, value, value))
+    }
     /// Create an Arc<Texture> checker texture from scale and two Colors.
     pub fn checker(scale: f64, even: Color, odd: Color) -> Arc<Texture> {
         Arc::new(Texture::CheckerTexture(CheckerTexture::from_colors(scale, even, odd)))
@@ -0,0 +1,490 @@
+use std::ops::Mul;
+
+use crate::hittable::AABB;
+use crate::prelude::*;
+
+/// An affine transform as a 4x4 matrix acting on column vectors, stored with its inverse.
+/// Chained methods apply in reading order: `Transform::scaling(s).rotate(axis, a).translate(t)`
+/// scales first, then rotates, then translates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    m: [[f64; 4]; 4],
+    inv: [[f64; 4]; 4],
+}
+
+impl Transform {
+    // ----- Constructors -----
+
+    /// The transform that leaves everything in place.
+    pub fn identity() -> Self {
+        Self { m: IDENTITY, inv: IDENTITY }
+    }
+
+    /// Constructor from a row-major 4x4 matrix, or None if it cannot be inverted.
+    pub fn from_matrix(m: [[f64; 4]; 4]) -> Option<Self> {
+        invert(&m).map(|inv| Self { m, inv })
+    }
+
+    /// Move by an offset.
+    pub fn translation(offset: Vec3) -> Self {
+        let mut m = IDENTITY;
+        let mut inv = IDENTITY;
+        for i in 0..3 {
+            m[i][3] = offset[i];
+            inv[i][3] = -offset[i];
+        }
+        Self { m, inv }
+    }
+
+    /// Scale along each axis, possibly by different amounts or negatively to mirror.
+    /// Panics if a factor is zero, which would flatten the object.
+    pub fn scaling(factors: Vec3) -> Self {
+        assert!(factors.x() != 0.0 && factors.y() != 0.0 && factors.z() != 0.0, "scale factors must be non-zero");
+        let mut m = IDENTITY;
+        let mut inv = IDENTITY;
+        for i in 0..3 {
+            m[i][i] = factors[i];
+            inv[i][i] = 1.0 / factors[i];
+        }
+        Self { m, inv }
+    }
+
+    /// Rotate counter-clockwise around an arbitrary axis through the origin, by an angle in degrees.
+    pub fn rotation(axis: Vec3, angle_deg: f64) -> Self {
+        let a = Vec3::unit_vector(&axis);
+        let (sin, cos) = degrees_to_radians(angle_deg).sin_cos();
+        let t = 1.0 - cos;
+
+        // Rodrigues' rotation formula
+        let m = [
+            [t * a.x() * a.x() + cos, t * a.x() * a.y() - sin * a.z(), t * a.x() * a.z() + sin * a.y(), 0.0],
+            [t * a.x() * a.y() + sin * a.z(), t * a.y() * a.y() + cos, t * a.y() * a.z() - sin * a.x(), 0.0],
+            [t * a.x() * a.z() - sin * a.y(), t * a.y() * a.z() + sin * a.x(), t * a.z() * a.z() + cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        Self { m, inv: transpose(&m) } // Rotations are orthogonal
+    }
+
+    /// Shear each coordinate in proportion to the others, e.g. `xy` moves x by `xy` times y.
+    pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        let m = [
+            [1.0, xy, xz, 0.0],
+            [yx, 1.0, yz, 0.0],
+            [zx, zy, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        Self::from_matrix(m).expect("shear must be invertible")
+    }
+
+    // ----- Chaining -----
+
+    /// Apply `next` after this transform.
+    pub fn then(&self, next: &Transform) -> Self {
+        *next * *self
+    }
+
+    /// Translate after this transform.
+    pub fn translate(&self, offset: Vec3) -> Self {
+        self.then(&Self::translation(offset))
+    }
+
+    /// Scale after this transform.
+    pub fn scale(&self, factors: Vec3) -> Self {
+        self.then(&Self::scaling(factors))
+    }
+
+    /// Rotate around an axis through the origin after this transform.
+    pub fn rotate(&self, axis: Vec3, angle_deg: f64) -> Self {
+        self.then(&Self::rotation(axis, angle_deg))
+    }
+
+    /// The transform undoing this one.
+    pub fn inverse(&self) -> Self {
+        Self { m: self.inv, inv: self.m }
+    }
+
+    // ----- Application -----
+
+    /// Transform a point, including translation.
+    #[inline]
+    pub fn point(&self, p: &Point3) -> Point3 {
+        apply(&self.m, p, 1.0)
+    }
+
+    /// Transform a direction, ignoring translation.
+    #[inline]
+    pub fn vector(&self, v: &Vec3) -> Vec3 {
+        apply(&self.m, v, 0.0)
+    }
+
+    /// Transform a surface normal with the inverse transpose, which keeps it perpendicular to the
+    /// surface under non-uniform scale and shear. The result is not normalized.
+    #[inline]
+    pub fn normal(&self, n: &Vec3) -> Vec3 {
+        Vec3::new(
+            self.inv[0][0] * n.x() + self.inv[1][0] * n.y() + self.inv[2][0] * n.z(),
+            self.inv[0][1] * n.x() + self.inv[1][1] * n.y() + self.inv[2][1] * n.z(),
+            self.inv[0][2] * n.x() + self.inv[1][2] * n.y() + self.inv[2][2] * n.z(),
+        )
+    }
+
+    /// Transform a ray. The direction is not renormalized, so hit distances `t` carry over unchanged.
+    #[inline]
+    pub fn ray(&self, r: &Ray) -> Ray {
+        Ray::new_with_time(self.point(&r.origin), self.vector(&r.direction), r.time)
+    }
+
+    /// Undo the transform on a point.
+    #[inline]
+    pub fn inverse_point(&self, p: &Point3) -> Point3 {
+        apply(&self.inv, p, 1.0)
+    }
+
+    /// Undo the transform on a direction.
+    #[inline]
+    pub fn inverse_vector(&self, v: &Vec3) -> Vec3 {
+        apply(&self.inv, v, 0.0)
+    }
+
+    /// Undo the transform on a ray, keeping hit distances `t` unchanged.
+    #[inline]
+    pub fn inverse_ray(&self, r: &Ray) -> Ray {
+        Ray::new_with_time(self.inverse_point(&r.origin), self.inverse_vector(&r.direction), r.time)
+    }
+
+    /// Bounding box of a transformed box, from its eight transformed corners.
+    pub fn bounding_box(&self, bbox: &AABB) -> AABB {
+        let mut min = Point3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        let mut max = Point3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for x in [bbox.x.min, bbox.x.max] {
+            for y in [bbox.y.min, bbox.y.max] {
+                for z in [bbox.z.min, bbox.z.max] {
+                    let corner = self.point(&Point3::new(x, y, z));
+                    for i in 0..3 {
+                        min[i] = min[i].min(corner[i]);
+                        max[i] = max[i].max(corner[i]);
+                    }
+                }
+            }
+        }
+        AABB::from_ordered_corners(&min, &max)
+    }
+
+    /// Determinant of the linear part: the factor by which volumes scale, negative if mirrored.
+    pub fn determinant(&self) -> f64 {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+// ----- Keyframed motion -----
+
+/// A transform at a point in time, decomposed so it can be interpolated: scale, then rotate about
+/// the origin, then translate.
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframe {
+    time: f64,
+    scale: Vec3,
+    rotation: Quaternion,
+    translation: Vec3,
+}
+
+impl Keyframe {
+    /// Constructor for the identity transform at the given time.
+    pub fn new(time: f64) -> Self {
+        Self {
+            time,
+            scale: Vec3::new(1.0, 1.0, 1.0),
+            rotation: Quaternion::IDENTITY,
+            translation: Vec3::zero(),
+        }
+    }
+
+    /// Set the scale along each axis.
+    pub fn with_scale(mut self, factors: Vec3) -> Self {
+        assert!(factors.x() != 0.0 && factors.y() != 0.0 && factors.z() != 0.0, "scale factors must be non-zero");
+        self.scale = factors;
+        self
+    }
+
+    /// Rotate around an axis by an angle in degrees, after any rotation already set.
+    pub fn with_rotation(mut self, axis: Vec3, angle_deg: f64) -> Self {
+        self.rotation = Quaternion::from_axis_angle(&axis, degrees_to_radians(angle_deg)) * self.rotation;
+        self
+    }
+
+    /// Set the translation, applied last.
+    pub fn with_translation(mut self, offset: Vec3) -> Self {
+        self.translation = offset;
+        self
+    }
+
+    /// The time of the keyframe.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// The keyframe as a matrix transform.
+    pub fn transform(&self) -> Transform {
+        let rotation = self.rotation.to_matrix();
+        Transform::scaling(self.scale)
+            .then(&Transform { m: rotation, inv: transpose(&rotation) })
+            .translate(self.translation)
+    }
+
+    /// Interpolate between two keyframes: linearly for scale and translation, and along the
+    /// shortest arc for rotation.
+    fn lerp(a: &Keyframe, b: &Keyframe, t: f64) -> Keyframe {
+        Keyframe {
+            time: a.time + t * (b.time - a.time),
+            scale: a.scale + t * (b.scale - a.scale),
+            rotation: Quaternion::slerp(&a.rotation, &b.rotation, t),
+            translation: a.translation + t * (b.translation - a.translation),
+        }
+    }
+}
+
+/// A transform changing over time, interpolated between keyframes and held constant before the
+/// first and after the last. Rotations take the shortest path between neighbouring keyframes,
+/// so spins of half a turn or more need intermediate keyframes.
+#[derive(Debug, Clone)]
+pub struct AnimatedTransform {
+    keys: Vec<Keyframe>, // Sorted by time
+}
+
+impl AnimatedTransform {
+    /// Constructor from keyframes in any order. Panics if there are none.
+    pub fn new(mut keys: Vec<Keyframe>) -> Self {
+        assert!(!keys.is_empty(), "an animated transform needs at least one keyframe");
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self { keys }
+    }
+
+    /// Constructor for motion from one keyframe to another.
+    pub fn between(start: Keyframe, end: Keyframe) -> Self {
+        Self::new(vec![start, end])
+    }
+
+    /// Time of the first keyframe.
+    pub fn start_time(&self) -> f64 {
+        self.keys[0].time
+    }
+
+    /// The transform at the given time.
+    pub fn at(&self, time: f64) -> Transform {
+        self.key_at(time).transform()
+    }
+
+    /// Bounding box of a box over the whole animation. Each segment between keyframes is sampled
+    /// in steps, and every sample is padded by the most a rotating corner can bulge out between steps.
+    pub fn bounding_box(&self, bbox: &AABB) -> AABB {
+        const STEPS: usize = 16;
+
+        // Farthest the box reaches from the origin, which rotations pivot around
+        let mut reach: f64 = 0.0;
+        for x in [bbox.x.min, bbox.x.max] {
+            for y in [bbox.y.min, bbox.y.max] {
+                for z in [bbox.z.min, bbox.z.max] {
+                    reach = reach.max(Vec3::new(x, y, z).length());
+                }
+            }
+        }
+
+        let mut result = self.keys[0].transform().bounding_box(bbox);
+        for pair in self.keys.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let scale = a.scale.x().abs().max(a.scale.y().abs()).max(a.scale.z().abs())
+                .max(b.scale.x().abs().max(b.scale.y().abs()).max(b.scale.z().abs()));
+            let half_step = Quaternion::angle_between(&a.rotation, &b.rotation) / (2.0 * STEPS as f64);
+            let pad = reach * scale * (1.0 / half_step.cos() - 1.0);
+
+            for step in 1..=STEPS {
+                let key = Keyframe::lerp(a, b, step as f64 / STEPS as f64);
+                let sample = key.transform().bounding_box(bbox);
+                let min = Point3::new(sample.x.min - pad, sample.y.min - pad, sample.z.min - pad);
+                let max = Point3::new(sample.x.max + pad, sample.y.max + pad, sample.z.max + pad);
+                result = AABB::merge(&result, &AABB::from_ordered_corners(&min, &max));
+            }
+        }
+        result
+    }
+
+    /// The interpolated keyframe at the given time.
+    fn key_at(&self, time: f64) -> Keyframe {
+        let first = &self.keys[0];
+        let last = &self.keys[self.keys.len() - 1];
+        if time <= first.time {
+            return *first;
+        }
+        if time >= last.time {
+            return *last;
+        }
+
+        let next = self.keys.partition_point(|key| key.time <= time);
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        let span = b.time - a.time;
+        if span <= 0.0 {
+            return *b;
+        }
+        Keyframe::lerp(a, b, (time - a.time) / span)
+    }
+}
+
+// ----- Quaternion -----
+
+/// A unit quaternion representing a rotation, for interpolating between orientations.
+#[derive(Debug, Clone, Copy)]
+struct Quaternion {
+    w: f64,
+    v: Vec3,
+}
+
+impl Quaternion {
+    const IDENTITY: Quaternion = Quaternion { w: 1.0, v: Vec3 { e: [0.0, 0.0, 0.0] } };
+
+    fn from_axis_angle(axis: &Vec3, radians: f64) -> Self {
+        let (sin, cos) = (0.5 * radians).sin_cos();
+        Self { w: cos, v: sin * Vec3::unit_vector(axis) }
+    }
+
+    fn dot(a: &Quaternion, b: &Quaternion) -> f64 {
+        a.w * b.w + Vec3::dot(&a.v, &b.v)
+    }
+
+    /// Rotation angle from one orientation to another, along the shortest arc.
+    fn angle_between(a: &Quaternion, b: &Quaternion) -> f64 {
+        2.0 * Self::dot(a, b).abs().min(1.0).acos()
+    }
+
+    /// Spherical linear interpolation along the shortest arc.
+    fn slerp(a: &Quaternion, b: &Quaternion, t: f64) -> Quaternion {
+        // q and -q are the same rotation; flip to take the short way around
+        let mut cos = Self::dot(a, b);
+        let b = if cos < 0.0 {
+            cos = -cos;
+            Quaternion { w: -b.w, v: -b.v }
+        } else {
+            *b
+        };
+
+        let (wa, wb) = if cos > 0.9995 {
+            (1.0 - t, t) // Nearly parallel: lerp, normalized below
+        } else {
+            let theta = cos.acos();
+            let sin = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        let q = Quaternion { w: wa * a.w + wb * b.w, v: wa * a.v + wb * b.v };
+        let norm = (q.w * q.w + q.v.length_squared()).sqrt();
+        Quaternion { w: q.w / norm, v: q.v / norm }
+    }
+
+    /// Rotation matrix of the quaternion.
+    fn to_matrix(self) -> [[f64; 4]; 4] {
+        let (w, x, y, z) = (self.w, self.v.x(), self.v.y(), self.v.z());
+        [
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y), 0.0],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x), 0.0],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+}
+
+/// Quaternion product: `a * b` rotates by `b`, then by `a`.
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, other: Quaternion) -> Quaternion {
+        Quaternion {
+            w: self.w * other.w - Vec3::dot(&self.v, &other.v),
+            v: self.w * other.v + other.w * self.v + Vec3::cross(&self.v, &other.v),
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Matrix product: `a * b` applies `b` first, then `a`.
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, other: Transform) -> Transform {
+        Transform { m: multiply(&self.m, &other.m), inv: multiply(&other.inv, &self.inv) }
+    }
+}
+
+// ---------------- Matrix helpers ----------------
+
+const IDENTITY: [[f64; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// Apply a matrix to (v, w), dropping the homogeneous coordinate.
+#[inline]
+fn apply(m: &[[f64; 4]; 4], v: &Vec3, w: f64) -> Vec3 {
+    Vec3::new(
+        m[0][0] * v.x() + m[0][1] * v.y() + m[0][2] * v.z() + m[0][3] * w,
+        m[1][0] * v.x() + m[1][1] * v.y() + m[1][2] * v.z() + m[1][3] * w,
+        m[2][0] * v.x() + m[2][1] * v.y() + m[2][2] * v.z() + m[2][3] * w,
+    )
+}
+
+fn multiply(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    let mut out = [[0.0; 4]; 4];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+fn transpose(m: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    let mut out = [[0.0; 4]; 4];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = m[j][i];
+        }
+    }
+    out
+}
+
+/// Invert a matrix by Gauss-Jordan elimination with partial pivoting, or None if it is singular.
+fn invert(m: &[[f64; 4]; 4]) -> Option<[[f64; 4]; 4]> {
+    let mut a = *m;
+    let mut inv = IDENTITY;
+    for col in 0..4 {
+        let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let scale = 1.0 / a[col][col];
+        for k in 0..4 {
+            a[col][k] *= scale;
+            inv[col][k] *= scale;
+        }
+        for row in 0..4 {
+            if row != col {
+                let factor = a[row][col];
+                for k in 0..4 {
+                    a[row][k] -= factor * a[col][k];
+                    inv[row][k] -= factor * inv[col][k];
+                }
+            }
+        }
+    }
+    Some(inv)
+}
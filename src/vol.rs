@@ -0,0 +1,152 @@
+use std::fs::File;
+use std::io::{self, BufReader, Error, ErrorKind, Read};
+use std::path::Path;
+
+use crate::vec3::Point3;
+
+/// A dense density grid read from a volume file.
+pub struct VolData {
+    pub resolution: (usize, usize, usize),
+    pub density: Vec<f32>, // x varies fastest, then y, then z
+    pub bounds_min: Point3, // World-space box the grid spans
+    pub bounds_max: Point3,
+}
+
+/// Mitsuba voxel data encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding { Float32, Float16, UInt8 }
+
+/// Load a Mitsuba `.vol` grid (version 3, binary little-endian). Multi-channel grids are averaged
+/// to a single density.
+pub fn load(path: impl AsRef<Path>) -> io::Result<VolData> {
+    println!("Loading volume file: {}", path.as_ref().display());
+    let mut reader = BufReader::new(File::open(path)?);
+
+    // Header: "VOL", version byte, encoding, resolution, channels, bounding box
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic[..3] != b"VOL" {
+        return Err(Error::new(ErrorKind::InvalidData, "Not a .vol file (missing VOL magic)"));
+    }
+    if magic[3] != 3 {
+        return Err(Error::new(ErrorKind::Unsupported, format!("Unsupported .vol version: {}", magic[3])));
+    }
+
+    let encoding = match read_i32(&mut reader)? {
+        1 => Encoding::Float32,
+        2 => Encoding::Float16,
+        3 => Encoding::UInt8,
+        other => return Err(Error::new(ErrorKind::Unsupported,
+            format!("Unsupported .vol encoding: {}", other))),
+    };
+    let nx = read_dimension(&mut reader)?;
+    let ny = read_dimension(&mut reader)?;
+    let nz = read_dimension(&mut reader)?;
+    let channels = read_dimension(&mut reader)?;
+
+    let mut bounds = [0.0; 6];
+    for b in &mut bounds {
+        *b = read_f32(&mut reader)? as f64;
+    }
+
+    // Voxel data, channels interleaved
+    let count = voxel_count(nx, ny, nz)?;
+    let mut density = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut sum = 0.0;
+        for _ in 0..channels {
+            sum += match encoding {
+                Encoding::Float32 => read_f32(&mut reader)?,
+                Encoding::Float16 => f16_to_f32(read_u16(&mut reader)?),
+                Encoding::UInt8 => read_u8(&mut reader)? as f32 / 255.0,
+            };
+        }
+        density.push(sum / channels as f32);
+    }
+
+    Ok(VolData {
+        resolution: (nx, ny, nz),
+        density,
+        bounds_min: Point3::new(bounds[0], bounds[1], bounds[2]),
+        bounds_max: Point3::new(bounds[3], bounds[4], bounds[5]),
+    })
+}
+
+/// Load a headerless grid of little-endian f32 densities with the given resolution, x varying
+/// fastest. Raw files carry no placement, so the grid spans the unit cube.
+pub fn load_raw(path: impl AsRef<Path>, resolution: (usize, usize, usize)) -> io::Result<VolData> {
+    println!("Loading raw volume file: {}", path.as_ref().display());
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let (nx, ny, nz) = resolution;
+    if nx == 0 || ny == 0 || nz == 0 {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Invalid raw volume resolution: {:?}", resolution)));
+    }
+    let count = voxel_count(nx, ny, nz)?;
+    let mut density = Vec::with_capacity(count);
+    for _ in 0..count {
+        density.push(read_f32(&mut reader)?);
+    }
+
+    Ok(VolData {
+        resolution,
+        density,
+        bounds_min: Point3::new(0.0, 0.0, 0.0),
+        bounds_max: Point3::new(1.0, 1.0, 1.0),
+    })
+}
+
+// ---------------- Binary helpers ----------------
+
+fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_i32<R: Read>(reader: &mut R) -> io::Result<i32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(i32::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(reader: &mut R) -> io::Result<f32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+/// Read a grid dimension, which must be positive.
+fn read_dimension<R: Read>(reader: &mut R) -> io::Result<usize> {
+    let n = read_i32(reader)?;
+    if n <= 0 {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Invalid .vol dimension: {}", n)));
+    }
+    Ok(n as usize)
+}
+
+/// Number of voxels in a grid, which must fit in memory.
+fn voxel_count(nx: usize, ny: usize, nz: usize) -> io::Result<usize> {
+    nx.checked_mul(ny)
+        .and_then(|n| n.checked_mul(nz))
+        .filter(|&n| n <= isize::MAX as usize / size_of::<f32>())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Volume grid too large: {}x{}x{}", nx, ny, nz)))
+}
+
+/// Convert an IEEE half-precision float to f32.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24), // Subnormal
+        31 => if mantissa == 0.0 { sign * f32::INFINITY } else { f32::NAN },
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
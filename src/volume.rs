@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use crate::hittable::AABB;
+use crate::noise::Noise;
+use crate::prelude::*;
+use crate::vol::VolData;
+
+// Density fields for heterogeneous media. Densities are unitless and scaled to an extinction
+// coefficient by the medium, which also needs the field's maximum as a majorant for tracking.
+
+// ----- Enum for different density fields -----
+
+/// A scalar density that varies through space.
+#[derive(Clone)]
+pub enum DensityField {
+    Grid(VoxelGrid),
+    Noise(NoiseDensity),
+}
+
+impl DensityField {
+    /// Density at a world-space point.
+    #[inline]
+    pub fn density(&self, p: &Point3) -> f64 {
+        match self {
+            DensityField::Grid(grid) => grid.density(p),
+            DensityField::Noise(noise) => noise.density(p),
+        }
+    }
+
+    /// An upper bound on the density anywhere in the field.
+    pub fn max_density(&self) -> f64 {
+        match self {
+            DensityField::Grid(grid) => grid.max_density(),
+            DensityField::Noise(noise) => noise.max_density(),
+        }
+    }
+}
+
+impl From<VoxelGrid> for DensityField {
+    fn from(grid: VoxelGrid) -> Self {
+        DensityField::Grid(grid)
+    }
+}
+
+impl From<NoiseDensity> for DensityField {
+    fn from(noise: NoiseDensity) -> Self {
+        DensityField::Noise(noise)
+    }
+}
+
+// ----- Voxel Grid -----
+
+/// A dense 3D grid of density samples stretched over a world-space box and trilinearly
+/// interpolated between voxel centers. Points outside the box have zero density.
+#[derive(Clone)]
+pub struct VoxelGrid {
+    nx: usize,
+    ny: usize,
+    nz: usize,
+    data: Vec<f32>, // x varies fastest, then y, then z
+    bounds: AABB,
+    max_density: f64,
+}
+
+impl VoxelGrid {
+    /// Constructor from voxel values of an `nx` x `ny` x `nz` grid, x varying fastest, spanning `bounds`.
+    pub fn new(nx: usize, ny: usize, nz: usize, data: Vec<f32>, bounds: AABB) -> Self {
+        assert_eq!(data.len(), nx * ny * nz, "voxel data does not match the grid resolution");
+        let max_density = data.iter().fold(0.0_f32, |m, &d| m.max(d)) as f64;
+        Self { nx, ny, nz, data, bounds, max_density }
+    }
+
+    /// Build a grid by evaluating `density` at each voxel center, given in grid coordinates [0,1]^3.
+    pub fn from_fn(nx: usize, ny: usize, nz: usize, bounds: AABB, density: impl Fn(&Point3) -> f64) -> Self {
+        let mut data = Vec::with_capacity(nx * ny * nz);
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let p = Point3::new(
+                        (x as f64 + 0.5) / nx as f64,
+                        (y as f64 + 0.5) / ny as f64,
+                        (z as f64 + 0.5) / nz as f64,
+                    );
+                    data.push(density(&p).max(0.0) as f32);
+                }
+            }
+        }
+        Self::new(nx, ny, nz, data, bounds)
+    }
+
+    /// Place the grid in the world by stretching it over the given box, in place of the bounds it
+    /// was built or loaded with. Rotate it by instancing the medium.
+    pub fn with_bounds(mut self, bounds: AABB) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// The world-space box the grid spans.
+    pub fn bounds(&self) -> &AABB {
+        &self.bounds
+    }
+
+    /// Trilinearly interpolated density at a world-space point.
+    pub fn density(&self, p: &Point3) -> f64 {
+        let (x, y, z) = (&self.bounds.x, &self.bounds.y, &self.bounds.z);
+        if !x.contains(p.x()) || !y.contains(p.y()) || !z.contains(p.z()) {
+            return 0.0;
+        }
+
+        // Continuous voxel coordinates, with voxel centers at integers
+        let gx = (p.x() - x.min) / x.size() * self.nx as f64 - 0.5;
+        let gy = (p.y() - y.min) / y.size() * self.ny as f64 - 0.5;
+        let gz = (p.z() - z.min) / z.size() * self.nz as f64 - 0.5;
+
+        let (x0, fx) = Self::split(gx, self.nx);
+        let (y0, fy) = Self::split(gy, self.ny);
+        let (z0, fz) = Self::split(gz, self.nz);
+
+        let mut accum = 0.0;
+        for (dz, wz) in [(0, 1.0 - fz), (1, fz)] {
+            for (dy, wy) in [(0, 1.0 - fy), (1, fy)] {
+                for (dx, wx) in [(0, 1.0 - fx), (1, fx)] {
+                    accum += wx * wy * wz * self.voxel(x0 + dx, y0 + dy, z0 + dz);
+                }
+            }
+        }
+        accum
+    }
+
+    /// The largest voxel value, which bounds the interpolated density.
+    pub fn max_density(&self) -> f64 {
+        self.max_density
+    }
+
+    /// Voxel value, clamping indices to the grid.
+    #[inline]
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f64 {
+        let x = x.min(self.nx - 1);
+        let y = y.min(self.ny - 1);
+        let z = z.min(self.nz - 1);
+        self.data[(z * self.ny + y) * self.nx + x] as f64
+    }
+
+    /// Split a continuous voxel coordinate into the lower voxel index and the fraction towards the next.
+    #[inline]
+    fn split(g: f64, n: usize) -> (usize, f64) {
+        let g = g.clamp(0.0, (n - 1) as f64);
+        let i = g.floor();
+        (i as usize, g - i)
+    }
+}
+
+impl From<VolData> for VoxelGrid {
+    fn from(data: VolData) -> Self {
+        let (nx, ny, nz) = data.resolution;
+        VoxelGrid::new(nx, ny, nz, data.density, AABB::from_corners(&data.bounds_min, &data.bounds_max))
+    }
+}
+
+// ----- Procedural Noise Density -----
+
+/// Density from Perlin turbulence, for smoke and clouds without a voxel grid.
+/// Turbulence below `threshold` is empty space, which breaks the medium into wisps.
+#[derive(Clone)]
+pub struct NoiseDensity {
+    noise: Arc<Noise>,
+    frequency: f64,
+    octaves: usize,
+    threshold: f64,
+}
+
+impl NoiseDensity {
+    /// Constructor from the noise frequency (features per world unit) and number of turbulence octaves.
+    pub fn new(frequency: f64, octaves: usize) -> Self {
+        Self {
+            noise: Arc::new(Noise::perlin()),
+            frequency,
+            octaves: octaves.max(1),
+            threshold: 0.0,
+        }
+    }
+
+    /// Set the turbulence value below which the medium is empty.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.max(0.0);
+        self
+    }
+
+    /// Density at a world-space point.
+    #[inline]
+    pub fn density(&self, p: &Point3) -> f64 {
+        let turbulence = self.noise.turbulence(&(self.frequency * *p), self.octaves);
+        (turbulence - self.threshold).max(0.0)
+    }
+
+    /// Bound on the density: each octave of Perlin noise lies in [-1,1] with halving weights.
+    pub fn max_density(&self) -> f64 {
+        let bound = 2.0 * (1.0 - 0.5_f64.powi(self.octaves as i32));
+        (bound - self.threshold).max(0.0)
+    }
+}